@@ -1,25 +1,92 @@
 pub use blake2b_rs::{Blake2b, Blake2bBuilder};
 
 use std::{
-    fs::File,
-    io::{BufWriter, Read, Write},
-    path::Path,
+    env,
+    fmt::Write,
+    fs::{self, File},
+    io::Read,
+    path::{Path, PathBuf},
 };
 
 const BUF_SIZE: usize = 8 * 1024;
 const CKB_HASH_PERSONALIZATION: &[u8] = b"ckb-default-hash";
 
+/// Default location of the shared binary built from the submodule
+const DEFAULT_LIB_PATH: &str =
+    "../ckb-miscellaneous-scripts/build/secp256k1_blake2b_sighash_all_dual";
+/// Overrides the location of the shared binary
+const LIB_PATH_ENV: &str = "CKB_LIB_SECP256K1_PATH";
+/// Set to `1` to enable strict mode, same as the `strict` feature
+const STRICT_ENV: &str = "CKB_LIB_SECP256K1_STRICT";
+/// Committed code hashes, used when the binary is absent
+const COMMITTED_CODE_HASHES: &str = "src/code_hashes.rs";
+
+/// Version name of the binary built from `ckb-miscellaneous-scripts`
+const CURRENT_VERSION: &str = "V1";
+/// Code hashes of previously released binaries, newest first.
+/// Old binaries stay on chain after an upgrade, keep them here so contracts can still load them.
+const PREVIOUS_VERSIONS: &[(&str, [u8; 32])] = &[];
+
 fn main() {
-    let path = Path::new("../ckb-miscellaneous-scripts/build/secp256k1_blake2b_sighash_all_dual");
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={}", COMMITTED_CODE_HASHES);
+    println!("cargo:rerun-if-env-changed={}", LIB_PATH_ENV);
+    println!("cargo:rerun-if-env-changed={}", STRICT_ENV);
+
+    let path: PathBuf = env::var_os(LIB_PATH_ENV)
+        .map(Into::into)
+        .unwrap_or_else(|| DEFAULT_LIB_PATH.into());
+
+    // strict mode requires the binary and the committed hashes to be up to date
+    let strict = env::var_os("CARGO_FEATURE_STRICT").is_some()
+        || env::var(STRICT_ENV).map(|v| v == "1").unwrap_or(false);
+
+    let committed = fs::read_to_string(COMMITTED_CODE_HASHES).expect("read code_hashes.rs");
+    let out_path = Path::new(&env::var("OUT_DIR").expect("OUT_DIR")).join("code_hashes.rs");
 
     if !path.exists() {
-        // do nothing if binary is not exists
+        if strict {
+            panic!(
+                "secp256k1 binary {} not found, set {} to the binary path",
+                path.display(),
+                LIB_PATH_ENV
+            );
+        }
+        // a missing file is treated as changed, track the nearest existing directory instead,
+        // so the hashes are regenerated once the binary is built
+        if let Some(dir) = path.ancestors().skip(1).find(|dir| dir.is_dir()) {
+            println!("cargo:rerun-if-changed={}", dir.display());
+        }
+        // fallback to the committed hashes
+        println!(
+            "cargo:warning=secp256k1 binary {} not found, use committed code hashes",
+            path.display()
+        );
+        fs::write(&out_path, committed).expect("write code_hashes.rs");
         return;
     }
 
-    let out_path = Path::new("src").join("code_hashes.rs");
-    let mut out_file = BufWriter::new(File::create(&out_path).expect("create code_hashes.rs"));
+    println!("cargo:rerun-if-changed={}", path.display());
+    let code_hashes = generate_code_hashes(&path);
+    if code_hashes != committed {
+        if strict {
+            panic!(
+                "code hash of {} differs from {}, copy {} over it",
+                path.display(),
+                COMMITTED_CODE_HASHES,
+                out_path.display()
+            );
+        }
+        println!(
+            "cargo:warning=code hash of {} differs from {}",
+            path.display(),
+            COMMITTED_CODE_HASHES
+        );
+    }
+    fs::write(&out_path, code_hashes).expect("write code_hashes.rs");
+}
 
+fn generate_code_hashes(path: &Path) -> String {
     let mut buf = [0u8; BUF_SIZE];
 
     // build hash
@@ -37,12 +104,27 @@ fn main() {
     let mut hash = [0u8; 32];
     blake2b.finalize(&mut hash);
 
-    write!(
-        &mut out_file,
-        "pub const CODE_HASH_SECP256K1: [u8; 32] = {:?};\n",
+    let mut out = String::new();
+    writeln!(
+        &mut out,
+        "pub const CODE_HASH_SECP256K1: [u8; 32] = {:?};",
         hash
     )
     .expect("write to code_hashes.rs");
+
+    writeln!(
+        &mut out,
+        "pub const CODE_HASHES_SECP256K1: &[(&str, [u8; 32])] = &["
+    )
+    .expect("write to code_hashes.rs");
+    let versions = Some((CURRENT_VERSION, hash))
+        .into_iter()
+        .chain(PREVIOUS_VERSIONS.iter().cloned());
+    for (name, hash) in versions {
+        writeln!(&mut out, "    ({:?}, {:?}),", name, hash).expect("write to code_hashes.rs");
+    }
+    writeln!(&mut out, "];").expect("write to code_hashes.rs");
+    out
 }
 
 pub fn new_blake2b() -> Blake2b {
@@ -0,0 +1,54 @@
+use crate::alloc::vec::Vec;
+use crate::error::Secp256k1Error;
+use crate::pubkey::Pubkey;
+use crate::signature::{Message, RecoverableSignature};
+use crate::{LibSecp256k1, PrefilledData};
+
+/// Error of batch recovery
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct BatchError {
+    /// Index of the failed item
+    pub index: usize,
+    pub error: Secp256k1Error,
+}
+
+impl LibSecp256k1 {
+    /// Recover pubkeys of the signature and message pairs
+    pub fn recover_pubkeys(
+        &self,
+        prefilled_data: &PrefilledData,
+        items: &[(RecoverableSignature, Message)],
+    ) -> Result<Vec<Pubkey>, BatchError> {
+        items
+            .iter()
+            .enumerate()
+            .map(|(index, (signature, message))| {
+                self.recover_pubkey(prefilled_data, signature, message)
+                    .map_err(|error| BatchError { index, error })
+            })
+            .collect()
+    }
+
+    /// Recover pubkeys without allocation, the pubkey of `items[i]` is written into `output[i]`,
+    /// returns `Secp256k1Error::InvalidOutputSize` if `output` is shorter than `items`
+    pub fn recover_pubkeys_into(
+        &self,
+        prefilled_data: &PrefilledData,
+        items: &[(RecoverableSignature, Message)],
+        output: &mut [Option<Pubkey>],
+    ) -> Result<(), BatchError> {
+        if output.len() < items.len() {
+            return Err(BatchError {
+                index: output.len(),
+                error: Secp256k1Error::InvalidOutputSize,
+            });
+        }
+        for (index, ((signature, message), pubkey)) in items.iter().zip(output).enumerate() {
+            let recovered = self
+                .recover_pubkey(prefilled_data, signature, message)
+                .map_err(|error| BatchError { index, error })?;
+            *pubkey = Some(recovered);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+//! Bitcoin compatible keys and signatures
+
+use crate::error::Secp256k1Error;
+use crate::hash::{hash160, sha256};
+use crate::pubkey::Pubkey;
+use crate::signature::{Message, RecoverableSignature, MESSAGE_SIZE, SIGNATURE_SIZE};
+
+/// Size of the hash160 of pubkey
+pub const HASH160_SIZE: usize = 20;
+/// Magic prefix of the bitcoin signed message, including its length byte
+pub const BTC_MESSAGE_PREFIX: &[u8] = b"\x18Bitcoin Signed Message:\n";
+
+/// Hash of the bitcoin signed message of `text`, the hash signed by bitcoin `signmessage`:
+/// double sha256 of prefix | text length (compact size) | text
+pub fn btc_message_hash(text: &[u8]) -> Message {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(BTC_MESSAGE_PREFIX);
+    let len = text.len() as u64;
+    if len < 0xfd {
+        hasher.update([len as u8]);
+    } else if len <= 0xffff {
+        hasher.update([0xfd]);
+        hasher.update((len as u16).to_le_bytes());
+    } else if len <= 0xffff_ffff {
+        hasher.update([0xfe]);
+        hasher.update((len as u32).to_le_bytes());
+    } else {
+        hasher.update([0xff]);
+        hasher.update(len.to_le_bytes());
+    }
+    hasher.update(text);
+    sha256(&hasher.finalize()).into()
+}
+
+/// The hash signed by bitcoin `signmessage` of the 32-bytes `message`,
+/// the signed text is the lowercase hex string of `message` so wallets can display it
+pub fn btc_signed_message(message: &Message) -> Message {
+    const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut text = [0u8; MESSAGE_SIZE * 2];
+    for (i, byte) in message.as_slice().iter().enumerate() {
+        text[i * 2] = HEX_CHARS[(byte >> 4) as usize];
+        text[i * 2 + 1] = HEX_CHARS[(byte & 0xf) as usize];
+    }
+    btc_message_hash(&text)
+}
+
+impl Pubkey {
+    /// RIPEMD160(SHA256(compressed pubkey)), as used in P2PKH addresses
+    pub fn hash160(&self) -> [u8; HASH160_SIZE] {
+        hash160(&self.0)
+    }
+}
+
+impl RecoverableSignature {
+    /// Parse a bitcoin signed message signature header | r | s,
+    /// the header must be 31..=34 which marks a compressed pubkey
+    pub fn from_btc_slice(data: &[u8]) -> Result<Self, Secp256k1Error> {
+        if data.len() != SIGNATURE_SIZE {
+            return Err(Secp256k1Error::InvalidSignatureSize);
+        }
+        let recovery_id = match data[0] {
+            header @ 31..=34 => header - 31,
+            _ => return Err(Secp256k1Error::ParseSignature),
+        };
+        let mut inner = [0u8; SIGNATURE_SIZE];
+        inner[..SIGNATURE_SIZE - 1].copy_from_slice(&data[1..]);
+        inner[SIGNATURE_SIZE - 1] = recovery_id;
+        RecoverableSignature::from_slice(&inner)
+    }
+}
@@ -0,0 +1,40 @@
+use crate::error::Secp256k1Error;
+use crate::pubkey::Pubkey;
+use crate::signature::{Message, RecoverableSignature};
+use crate::{LibSecp256k1, PrefilledData};
+
+impl LibSecp256k1 {
+    /// Load the prefilled data into the cache, does nothing if it's already cached
+    pub fn preload_prefilled_data(&mut self) -> Result<(), Secp256k1Error> {
+        if self.prefilled_data.is_none() {
+            self.prefilled_data = Some(self.load_prefilled_data()?);
+        }
+        Ok(())
+    }
+
+    /// Cache prefilled data loaded by the caller, e.g. from `PrefilledData::load_from_cell_dep`
+    pub fn set_prefilled_data(&mut self, prefilled_data: PrefilledData<'static>) {
+        self.prefilled_data = Some(prefilled_data);
+    }
+
+    /// Drop the cached prefilled data
+    pub fn drop_prefilled_data(&mut self) {
+        self.prefilled_data = None;
+    }
+
+    /// Recover pubkey with the cached prefilled data,
+    /// the prefilled data is loaded on the first call and reused afterwards
+    pub fn recover_pubkey_cached(
+        &mut self,
+        signature: &RecoverableSignature,
+        message: &Message,
+    ) -> Result<Pubkey, Secp256k1Error> {
+        if let Some(prefilled_data) = self.prefilled_data.as_ref() {
+            return self.recover_pubkey(prefilled_data, signature, message);
+        }
+        let prefilled_data = self.load_prefilled_data()?;
+        let pubkey = self.recover_pubkey(&prefilled_data, signature, message);
+        self.prefilled_data = Some(prefilled_data);
+        pubkey
+    }
+}
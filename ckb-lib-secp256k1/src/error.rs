@@ -0,0 +1,140 @@
+#[cfg(target_arch = "riscv64")]
+use ckb_std::{dynamic_loading::Error as DLError, error::SysError};
+
+/// Errors returned by the `secp256k1_blake2b_sighash_all_dual` library
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Secp256k1Error {
+    /// Invalid arguments length, e.g. the lock of the witness isn't a 65-bytes signature
+    ArgumentsLen,
+    /// Invalid molecule encoding
+    Encoding,
+    /// Syscall failed
+    Syscall,
+    /// Failed to recover pubkey from the signature
+    RecoverPubkey,
+    /// Signature verification failed
+    Verification,
+    /// Failed to parse pubkey
+    ParsePubkey,
+    /// Failed to parse signature
+    ParseSignature,
+    /// Failed to serialize pubkey
+    SerializePubkey,
+    /// Script is too long
+    ScriptTooLong,
+    /// Witness is too large
+    WitnessSize,
+    /// Blake160 of the recovered pubkey doesn't match
+    PubkeyBlake160Hash,
+    /// Buffer of prefilled data is too small
+    InvalidPrefilledDataSize,
+    /// Signature size is invalid
+    InvalidSignatureSize,
+    /// Message size is invalid
+    InvalidMessageSize,
+    /// Output buffer size is invalid
+    InvalidOutputSize,
+    /// The recovered pubkey doesn't match the expected one
+    PubkeyMismatch,
+    /// Can't find the `secp256k1_data` cell in the cell deps
+    DataCellNotFound,
+    /// Failed to allocate memory for prefilled data
+    OutOfMemory,
+    /// Unknown error code
+    Unknown(i32),
+}
+
+/// Error codes returned by the C library, codes below -100 are reserved for the errors raised
+/// by the rust side and are never returned by the library, so they convert to `Unknown`
+impl From<i32> for Secp256k1Error {
+    fn from(err_code: i32) -> Self {
+        use Secp256k1Error::*;
+        match err_code {
+            -1 => ArgumentsLen,
+            -2 => Encoding,
+            -3 => Syscall,
+            -11 => RecoverPubkey,
+            -12 => Verification,
+            -13 => ParsePubkey,
+            -14 => ParseSignature,
+            -15 => SerializePubkey,
+            -21 => ScriptTooLong,
+            -22 => WitnessSize,
+            -31 => PubkeyBlake160Hash,
+            -41 => InvalidPrefilledDataSize,
+            -42 => InvalidSignatureSize,
+            -43 => InvalidMessageSize,
+            -44 => InvalidOutputSize,
+            err_code => Unknown(err_code),
+        }
+    }
+}
+
+impl From<Secp256k1Error> for i32 {
+    fn from(err: Secp256k1Error) -> Self {
+        use Secp256k1Error::*;
+        match err {
+            ArgumentsLen => -1,
+            Encoding => -2,
+            Syscall => -3,
+            RecoverPubkey => -11,
+            Verification => -12,
+            ParsePubkey => -13,
+            ParseSignature => -14,
+            SerializePubkey => -15,
+            ScriptTooLong => -21,
+            WitnessSize => -22,
+            PubkeyBlake160Hash => -31,
+            InvalidPrefilledDataSize => -41,
+            InvalidSignatureSize => -42,
+            InvalidMessageSize => -43,
+            InvalidOutputSize => -44,
+            // codes below -100 are raised by the rust side
+            PubkeyMismatch => -101,
+            DataCellNotFound => -102,
+            OutOfMemory => -103,
+            Unknown(err_code) => err_code,
+        }
+    }
+}
+
+/// Errors of loading the library
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum LoadError {
+    /// Can't find the library cell in the cell deps
+    LibraryNotFound,
+    /// The buffer of the `CKBDLContext` is too small to load the library
+    ContextTooSmall,
+    /// The library cell isn't a valid shared library
+    InvalidLibrary,
+    /// Syscall failed while loading the library
+    Syscall,
+    /// The library doesn't export the symbol
+    SymbolMissing(&'static str),
+}
+
+#[cfg(target_arch = "riscv64")]
+impl From<DLError> for LoadError {
+    fn from(err: DLError) -> Self {
+        match err {
+            DLError::CellNotFound => LoadError::LibraryNotFound,
+            DLError::MemoryNotEnough => LoadError::ContextTooSmall,
+            DLError::Sys(_) => LoadError::Syscall,
+            _ => LoadError::InvalidLibrary,
+        }
+    }
+}
+
+#[cfg(target_arch = "riscv64")]
+impl From<SysError> for LoadError {
+    fn from(_err: SysError) -> Self {
+        LoadError::Syscall
+    }
+}
+
+#[cfg(target_arch = "riscv64")]
+impl From<SysError> for Secp256k1Error {
+    fn from(_err: SysError) -> Self {
+        Secp256k1Error::Syscall
+    }
+}
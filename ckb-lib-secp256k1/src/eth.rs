@@ -0,0 +1,48 @@
+//! Ethereum compatible keys and signatures
+
+use crate::error::Secp256k1Error;
+use crate::hash::keccak256;
+use crate::pubkey::Pubkey;
+use crate::signature::{Message, RecoverableSignature, MESSAGE_SIZE, SIGNATURE_SIZE};
+
+/// Size of the ethereum address
+pub const ETH_ADDRESS_SIZE: usize = 20;
+/// Prefix of the 32-bytes message signed by `personal_sign`
+pub const ETH_MESSAGE_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n32";
+
+/// The message actually signed by `personal_sign(message)`
+pub fn eth_personal_message(message: &Message) -> Message {
+    let mut data = [0u8; ETH_MESSAGE_PREFIX.len() + MESSAGE_SIZE];
+    data[..ETH_MESSAGE_PREFIX.len()].copy_from_slice(ETH_MESSAGE_PREFIX);
+    data[ETH_MESSAGE_PREFIX.len()..].copy_from_slice(message.as_slice());
+    keccak256(&data).into()
+}
+
+impl Pubkey {
+    /// Ethereum address: the last 20 bytes of keccak256 of the uncompressed pubkey without prefix
+    pub fn eth_address(&self) -> Result<[u8; ETH_ADDRESS_SIZE], Secp256k1Error> {
+        let uncompressed = self.to_uncompressed()?;
+        let hash = keccak256(&uncompressed[1..]);
+        let mut address = [0u8; ETH_ADDRESS_SIZE];
+        address.copy_from_slice(&hash[32 - ETH_ADDRESS_SIZE..]);
+        Ok(address)
+    }
+}
+
+impl RecoverableSignature {
+    /// Parse an ethereum signature r | s | v, v is 27/28 or the raw recovery id 0/1
+    pub fn from_eth_slice(data: &[u8]) -> Result<Self, Secp256k1Error> {
+        if data.len() != SIGNATURE_SIZE {
+            return Err(Secp256k1Error::InvalidSignatureSize);
+        }
+        let recovery_id = match data[SIGNATURE_SIZE - 1] {
+            v @ 0..=1 => v,
+            v @ 27..=28 => v - 27,
+            _ => return Err(Secp256k1Error::ParseSignature),
+        };
+        let mut inner = [0u8; SIGNATURE_SIZE];
+        inner.copy_from_slice(data);
+        inner[SIGNATURE_SIZE - 1] = recovery_id;
+        RecoverableSignature::from_slice(&inner)
+    }
+}
@@ -0,0 +1,64 @@
+//! Hash functions used to derive pubkey hashes
+
+use blake2b_ref::{Blake2b, Blake2bBuilder};
+
+const CKB_HASH_PERSONALIZATION: &[u8] = b"ckb-default-hash";
+
+pub fn new_blake2b() -> Blake2b {
+    Blake2bBuilder::new(32)
+        .personal(CKB_HASH_PERSONALIZATION)
+        .build()
+}
+
+pub fn blake2b_256(data: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    let mut hasher = new_blake2b();
+    hasher.update(data);
+    hasher.finalize(&mut hash);
+    hash
+}
+
+/// The first 20 bytes of the ckb blake2b hash
+pub fn blake160(data: &[u8]) -> [u8; 20] {
+    let mut hash = [0u8; 20];
+    hash.copy_from_slice(&blake2b_256(data)[..20]);
+    hash
+}
+
+/// Keccak-256 hash, as used by ethereum
+#[cfg(feature = "eth")]
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    use tiny_keccak::{Hasher, Keccak};
+
+    let mut hash = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(data);
+    hasher.finalize(&mut hash);
+    hash
+}
+
+/// SHA-256 hash
+#[cfg(feature = "btc")]
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&Sha256::digest(data));
+    hash
+}
+
+/// RIPEMD-160 hash
+#[cfg(feature = "btc")]
+pub fn ripemd160(data: &[u8]) -> [u8; 20] {
+    use ripemd160::{Digest, Ripemd160};
+
+    let mut hash = [0u8; 20];
+    hash.copy_from_slice(&Ripemd160::digest(data));
+    hash
+}
+
+/// RIPEMD-160 of SHA-256, as used in bitcoin P2PKH addresses
+#[cfg(feature = "btc")]
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    ripemd160(&sha256(data))
+}
@@ -0,0 +1,8 @@
+/// How a code hash locates the library cell
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum HashType {
+    /// Match the data hash of the cell
+    Data,
+    /// Match the type script hash of the cell
+    Type,
+}
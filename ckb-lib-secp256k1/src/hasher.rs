@@ -0,0 +1,54 @@
+use crate::error::Secp256k1Error;
+use crate::pubkey::Pubkey;
+
+/// Derives the identity stored in the lock args from a pubkey
+pub trait PubkeyHasher {
+    type Hash: AsRef<[u8]> + Default;
+
+    fn hash(pubkey: &Pubkey) -> Result<Self::Hash, Secp256k1Error>;
+
+    /// Size of the hash
+    fn hash_size() -> usize {
+        Self::Hash::default().as_ref().len()
+    }
+}
+
+/// The first 20 bytes of ckb blake2b hash of the compressed pubkey, as used by the secp256k1 lock
+#[derive(Debug, Clone, Copy)]
+pub struct Blake160;
+
+impl PubkeyHasher for Blake160 {
+    type Hash = [u8; 20];
+
+    fn hash(pubkey: &Pubkey) -> Result<Self::Hash, Secp256k1Error> {
+        Ok(pubkey.blake160())
+    }
+}
+
+/// Ethereum address of the pubkey
+#[cfg(feature = "eth")]
+#[derive(Debug, Clone, Copy)]
+pub struct KeccakAddress;
+
+#[cfg(feature = "eth")]
+impl PubkeyHasher for KeccakAddress {
+    type Hash = [u8; 20];
+
+    fn hash(pubkey: &Pubkey) -> Result<Self::Hash, Secp256k1Error> {
+        pubkey.eth_address()
+    }
+}
+
+/// RIPEMD160(SHA256(compressed pubkey)), as used by bitcoin P2PKH
+#[cfg(feature = "btc")]
+#[derive(Debug, Clone, Copy)]
+pub struct Hash160;
+
+#[cfg(feature = "btc")]
+impl PubkeyHasher for Hash160 {
+    type Hash = [u8; 20];
+
+    fn hash(pubkey: &Pubkey) -> Result<Self::Hash, Secp256k1Error> {
+        Ok(pubkey.hash160())
+    }
+}
@@ -1,9 +1,68 @@
 #![no_std]
 
 extern crate alloc;
+#[cfg(all(feature = "mock", not(target_arch = "riscv64")))]
+extern crate std;
 
-mod code_hashes;
+#[cfg(any(target_arch = "riscv64", feature = "mock"))]
+mod batch;
+#[cfg(feature = "btc")]
+mod btc;
+#[cfg(any(target_arch = "riscv64", feature = "mock"))]
+mod cached;
+mod code_hashes {
+    // generated by build.rs, falls back to the committed `src/code_hashes.rs`
+    include!(concat!(env!("OUT_DIR"), "/code_hashes.rs"));
+}
+mod error;
+#[cfg(feature = "eth")]
+mod eth;
+pub mod hash;
+mod hash_type;
+mod hasher;
+#[cfg(target_arch = "riscv64")]
 mod libsecp256k1;
+#[cfg(all(feature = "mock", not(target_arch = "riscv64")))]
+pub mod mock;
+#[cfg(target_arch = "riscv64")]
+mod prefilled_data;
+mod pubkey;
+mod secp256k1_data;
+#[cfg(target_arch = "riscv64")]
+mod sighash;
+mod signature;
+#[cfg(any(target_arch = "riscv64", feature = "mock"))]
+mod verify;
 
-pub use code_hashes::CODE_HASH_SECP256K1;
-pub use libsecp256k1::LibSecp256k1;
\ No newline at end of file
+#[cfg(any(target_arch = "riscv64", feature = "mock"))]
+pub use batch::BatchError;
+#[cfg(feature = "btc")]
+pub use btc::{btc_message_hash, btc_signed_message, BTC_MESSAGE_PREFIX, HASH160_SIZE};
+#[cfg(target_arch = "riscv64")]
+pub use ckb_std::{ckb_constants::Source, dynamic_loading::CKBDLContext};
+pub use code_hashes::{CODE_HASHES_SECP256K1, CODE_HASH_SECP256K1};
+pub use error::{LoadError, Secp256k1Error};
+#[cfg(feature = "eth")]
+pub use eth::{eth_personal_message, ETH_ADDRESS_SIZE, ETH_MESSAGE_PREFIX};
+pub use hash_type::HashType;
+#[cfg(feature = "btc")]
+pub use hasher::Hash160;
+#[cfg(feature = "eth")]
+pub use hasher::KeccakAddress;
+pub use hasher::{Blake160, PubkeyHasher};
+#[cfg(target_arch = "riscv64")]
+pub use libsecp256k1::LibSecp256k1;
+#[cfg(all(feature = "mock", not(target_arch = "riscv64")))]
+pub use mock::{
+    sighash_all_message, sighash_all_message_with_lock_prefix, CKBDLContext, LibSecp256k1,
+    PrefilledData, Source,
+};
+#[cfg(target_arch = "riscv64")]
+pub use prefilled_data::PrefilledData;
+pub use pubkey::{Pubkey, PUBKEY_SIZE, UNCOMPRESSED_PUBKEY_SIZE};
+pub use secp256k1_data::{CODE_HASH_SECP256K1_DATA, SECP256K1_DATA_SIZE};
+#[cfg(target_arch = "riscv64")]
+pub use sighash::{sighash_all_message, sighash_all_message_with_lock_prefix};
+pub use signature::{Message, RecoverableSignature, MESSAGE_SIZE, SIGNATURE_SIZE};
+#[cfg(any(target_arch = "riscv64", feature = "mock"))]
+pub use verify::ExpectedPubkey;
@@ -1,6 +1,21 @@
-use crate::code_hashes::CODE_HASH_SECP256K1;
-use crate::alloc::{alloc::{alloc, Layout}, boxed::Box};
-use ckb_std::dynamic_loading::{CKBDLContext, Symbol};
+use crate::alloc::{
+    alloc::{alloc, Layout},
+    boxed::Box,
+};
+use crate::code_hashes::{CODE_HASHES_SECP256K1, CODE_HASH_SECP256K1};
+use crate::error::{LoadError, Secp256k1Error};
+use crate::hash_type::HashType;
+use crate::prefilled_data::PrefilledData;
+use crate::pubkey::{Pubkey, PUBKEY_SIZE};
+use crate::secp256k1_data::SECP256K1_DATA_SIZE;
+use crate::signature::{Message, RecoverableSignature, MESSAGE_SIZE, SIGNATURE_SIZE};
+use ckb_std::{
+    ckb_constants::Source,
+    dynamic_loading::{CKBDLContext, Library, Symbol},
+    error::SysError,
+    high_level::{load_cell_data_hash, load_cell_type_hash},
+};
+use core::mem::MaybeUninit;
 
 /// function signature of validate_secp256k1_blake2b_sighash_all
 type ValidateBlake2bSighashAll = unsafe extern "C" fn(pubkey_hash: *const u8) -> i32;
@@ -19,109 +34,182 @@ type ValidateSignature = unsafe extern "C" fn(
 type LoadPrefilledData = unsafe extern "C" fn(data: *mut u8, len: *mut u64) -> i32;
 
 /// Symbol name
-const VALIDATE_BLAKE2B_SIGHASH_ALL: &[u8; 38] = b"validate_secp256k1_blake2b_sighash_all";
-const VALIDATE_SIGNATURE: &[u8; 18] = b"validate_signature";
-const LOAD_PREFILLED_DATA: &[u8; 19] = b"load_prefilled_data";
+const VALIDATE_BLAKE2B_SIGHASH_ALL: &str = "validate_secp256k1_blake2b_sighash_all";
+const VALIDATE_SIGNATURE: &str = "validate_signature";
+const LOAD_PREFILLED_DATA: &str = "load_prefilled_data";
 
-const SECP256K1_DATA_SIZE: usize = 1048576;
-pub struct PrefilledData(Box<[u8; SECP256K1_DATA_SIZE]>);
-pub struct Pubkey([u8; 33]);
-
-impl Pubkey {
-    pub fn as_slice(&self) -> &[u8] {
-        &self.0
-    }
+fn get_symbol<S>(lib: &Library, name: &'static str) -> Result<Symbol<S>, LoadError> {
+    unsafe { lib.get(name.as_bytes()) }.ok_or(LoadError::SymbolMissing(name))
 }
 
-impl Default for Pubkey {
-    fn default() -> Self {
-        let inner = [0u8; 33];
-        Pubkey(inner)
+/// Index of the cell dep whose type script hash equals to `type_hash`,
+/// unlike `QueryIter` it returns syscall errors instead of panicking
+fn find_cell_dep_by_type_hash(type_hash: &[u8; 32]) -> Result<usize, LoadError> {
+    for index in 0.. {
+        match load_cell_type_hash(index, Source::CellDep) {
+            Ok(Some(hash)) if &hash == type_hash => return Ok(index),
+            Ok(_) => continue,
+            Err(SysError::IndexOutOfBound) => break,
+            Err(err) => return Err(err.into()),
+        }
     }
+    Err(LoadError::LibraryNotFound)
 }
 
-impl Into<[u8; 33]> for Pubkey {
-    fn into(self) -> [u8; 33] {
-        self.0
-    }
-}
 pub struct LibSecp256k1 {
     validate_blake2b_sighash_all: Symbol<ValidateBlake2bSighashAll>,
     validate_signature: Symbol<ValidateSignature>,
     load_prefilled_data: Symbol<LoadPrefilledData>,
+    /// cached prefilled data, see `recover_pubkey_cached`
+    pub(crate) prefilled_data: Option<PrefilledData<'static>>,
 }
 
 impl LibSecp256k1 {
     pub fn load<T>(context: &mut CKBDLContext<T>) -> Self {
-        // load library
-        let lib = context.load(&CODE_HASH_SECP256K1).expect("load secp256k1");
+        Self::try_load(context).expect("load secp256k1")
+    }
 
-        // find symbols
-        let validate_blake2b_sighash_all: Symbol<ValidateBlake2bSighashAll> = unsafe {
-            lib.get(VALIDATE_BLAKE2B_SIGHASH_ALL)
-                .expect("load function")
+    /// Load the library, returns an error if the library cell or a symbol is missing
+    pub fn try_load<T>(context: &mut CKBDLContext<T>) -> Result<Self, LoadError> {
+        Self::try_load_with(context, &CODE_HASH_SECP256K1, HashType::Data)
+    }
+
+    pub fn load_by_type_hash<T>(context: &mut CKBDLContext<T>, type_hash: &[u8; 32]) -> Self {
+        Self::try_load_by_type_hash(context, type_hash).expect("load secp256k1")
+    }
+
+    /// Load the library from the cell dep whose type script hash equals to `type_hash`,
+    /// a type-id type script allows the library cell to be upgraded in place
+    pub fn try_load_by_type_hash<T>(
+        context: &mut CKBDLContext<T>,
+        type_hash: &[u8; 32],
+    ) -> Result<Self, LoadError> {
+        Self::try_load_with(context, type_hash, HashType::Type)
+    }
+
+    pub fn load_with<T>(
+        context: &mut CKBDLContext<T>,
+        code_hash: &[u8; 32],
+        hash_type: HashType,
+    ) -> Self {
+        Self::try_load_with(context, code_hash, hash_type).expect("load secp256k1")
+    }
+
+    /// Load the library located by `code_hash` and `hash_type`,
+    /// allows the caller to pick the library build, e.g. from script args
+    pub fn try_load_with<T>(
+        context: &mut CKBDLContext<T>,
+        code_hash: &[u8; 32],
+        hash_type: HashType,
+    ) -> Result<Self, LoadError> {
+        let lib = match hash_type {
+            HashType::Data => context.load(code_hash)?,
+            HashType::Type => {
+                // find the library cell by type hash, then load it by data hash
+                let index = find_cell_dep_by_type_hash(code_hash)?;
+                let data_hash = load_cell_data_hash(index, Source::CellDep)?;
+                context.load(&data_hash)?
+            }
         };
-        let validate_signature: Symbol<ValidateSignature> =
-            unsafe { lib.get(VALIDATE_SIGNATURE).expect("load function") };
-        let load_prefilled_data: Symbol<LoadPrefilledData> =
-            unsafe { lib.get(LOAD_PREFILLED_DATA).expect("load function") };
-        LibSecp256k1 {
+        Self::from_library(&lib)
+    }
+
+    pub fn load_any<T>(context: &mut CKBDLContext<T>) -> (Self, &'static str) {
+        Self::try_load_any(context).expect("load secp256k1")
+    }
+
+    /// Try the known library versions in `CODE_HASHES_SECP256K1` in order,
+    /// returns the library and the name of the loaded version
+    pub fn try_load_any<T>(
+        context: &mut CKBDLContext<T>,
+    ) -> Result<(Self, &'static str), LoadError> {
+        for (name, code_hash) in CODE_HASHES_SECP256K1.iter() {
+            match Self::try_load_with(context, code_hash, HashType::Data) {
+                Ok(lib) => return Ok((lib, *name)),
+                Err(LoadError::LibraryNotFound) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Err(LoadError::LibraryNotFound)
+    }
+
+    fn from_library(lib: &Library) -> Result<Self, LoadError> {
+        // find symbols
+        let validate_blake2b_sighash_all: Symbol<ValidateBlake2bSighashAll> =
+            get_symbol(lib, VALIDATE_BLAKE2B_SIGHASH_ALL)?;
+        let validate_signature: Symbol<ValidateSignature> = get_symbol(lib, VALIDATE_SIGNATURE)?;
+        let load_prefilled_data: Symbol<LoadPrefilledData> = get_symbol(lib, LOAD_PREFILLED_DATA)?;
+        Ok(LibSecp256k1 {
             validate_blake2b_sighash_all,
             load_prefilled_data,
             validate_signature,
-        }
+            prefilled_data: None,
+        })
     }
 
-    pub fn validate_blake2b_sighash_all(&self, pubkey_hash: &mut [u8; 20]) -> Result<(), i32> {
+    pub fn validate_blake2b_sighash_all(
+        &self,
+        pubkey_hash: &mut [u8; 20],
+    ) -> Result<(), Secp256k1Error> {
         let f = &self.validate_blake2b_sighash_all;
         let error_code = unsafe { f(pubkey_hash.as_mut_ptr()) };
         if error_code != 0 {
-            return Err(error_code);
+            return Err(error_code.into());
         }
         Ok(())
     }
 
-    pub fn load_prefilled_data(&self) -> Result<PrefilledData, i32> {
+    pub fn load_prefilled_data(&self) -> Result<PrefilledData<'static>, Secp256k1Error> {
+        // the library fills the whole buffer, don't waste cycles on zeroing it
         let mut data = unsafe {
-            let layout = Layout::new::<[u8; SECP256K1_DATA_SIZE]>();
-            let raw_allocation = alloc(layout) as *mut [u8; SECP256K1_DATA_SIZE];
+            let layout = Layout::new::<MaybeUninit<[u8; SECP256K1_DATA_SIZE]>>();
+            let raw_allocation = alloc(layout) as *mut MaybeUninit<[u8; SECP256K1_DATA_SIZE]>;
+            if raw_allocation.is_null() {
+                return Err(Secp256k1Error::OutOfMemory);
+            }
+            // allocated with the layout of `MaybeUninit`, the box frees it without reading
             Box::from_raw(raw_allocation)
         };
         let mut len: u64 = SECP256K1_DATA_SIZE as u64;
 
         let f = &self.load_prefilled_data;
-        let error_code = unsafe { f(data.as_mut_ptr(), &mut len as *mut u64) };
+        let error_code = unsafe { f(data.as_mut_ptr() as *mut u8, &mut len as *mut u64) };
         if error_code != 0 {
-            return Err(error_code);
+            return Err(error_code.into());
+        }
+        if len as usize != SECP256K1_DATA_SIZE {
+            return Err(Secp256k1Error::InvalidPrefilledDataSize);
         }
-        Ok(PrefilledData(data))
+        // the library succeeded and filled the whole buffer, so it's initialized
+        let data = unsafe { Box::from_raw(Box::into_raw(data) as *mut [u8; SECP256K1_DATA_SIZE]) };
+        PrefilledData::owned(data, len as usize)
     }
 
     pub fn recover_pubkey(
         &self,
         prefilled_data: &PrefilledData,
-        signature: &[u8],
-        message: &[u8],
-    ) -> Result<Pubkey, i32> {
-        let mut pubkey = Pubkey::default();
-        let mut len: u64 = pubkey.0.len() as u64;
+        signature: &RecoverableSignature,
+        message: &Message,
+    ) -> Result<Pubkey, Secp256k1Error> {
+        let mut pubkey = [0u8; PUBKEY_SIZE];
+        let mut len: u64 = pubkey.len() as u64;
 
         let f = &self.validate_signature;
         let error_code = unsafe {
             f(
-                prefilled_data.0.as_ptr(),
-                signature.as_ptr(),
-                signature.len() as u64,
-                message.as_ptr(),
-                message.len() as u64,
-                pubkey.0.as_mut_ptr(),
+                prefilled_data.as_ptr(),
+                signature.as_slice().as_ptr(),
+                SIGNATURE_SIZE as u64,
+                message.as_slice().as_ptr(),
+                MESSAGE_SIZE as u64,
+                pubkey.as_mut_ptr(),
                 &mut len as *mut u64,
             )
         };
         if error_code != 0 {
-            return Err(error_code);
+            return Err(error_code.into());
         }
-        debug_assert_eq!(pubkey.0.len() as u64, len);
-        Ok(pubkey)
+        debug_assert_eq!(pubkey.len() as u64, len);
+        Ok(Pubkey(pubkey))
     }
 }
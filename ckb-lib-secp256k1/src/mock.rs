@@ -0,0 +1,182 @@
+//! Host implementation of `LibSecp256k1` backed by the native secp256k1 library,
+//! it mirrors the public API of the dynamic loading one so contract logic can be tested with `cargo test`.
+//!
+//! The transaction isn't available on the host, the sighash-all message and the signature
+//! of the current thread are set by `set_sighash_all`.
+
+use crate::code_hashes::CODE_HASHES_SECP256K1;
+use crate::error::{LoadError, Secp256k1Error};
+use crate::hash_type::HashType;
+use crate::pubkey::Pubkey;
+use crate::secp256k1_data::SECP256K1_DATA_SIZE;
+use crate::signature::{Message, RecoverableSignature};
+use core::cell::Cell;
+use core::marker::PhantomData;
+use secp256k1::{recovery, Secp256k1};
+
+std::thread_local! {
+    static SIGHASH_ALL: Cell<Option<(Message, RecoverableSignature)>> = const { Cell::new(None) };
+}
+
+/// Set the sighash-all message and the signature in the witness of the current thread,
+/// which `sighash_all_message` returns and `validate_blake2b_sighash_all` recovers the pubkey hash from
+pub fn set_sighash_all(message: Message, signature: RecoverableSignature) {
+    SIGHASH_ALL.with(|sighash_all| sighash_all.set(Some((message, signature))));
+}
+
+/// Clear the sighash-all message and the signature of the current thread
+pub fn clear_sighash_all() {
+    SIGHASH_ALL.with(|sighash_all| sighash_all.set(None));
+}
+
+fn sighash_all() -> Option<(Message, RecoverableSignature)> {
+    SIGHASH_ALL.with(|sighash_all| sighash_all.get())
+}
+
+/// Mirror of `ckb_std::ckb_constants::Source`
+#[repr(u64)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Source {
+    Input = 1,
+    Output = 2,
+    CellDep = 3,
+    HeaderDep = 4,
+    GroupInput = 0x0100000000000001,
+    GroupOutput = 0x0100000000000002,
+}
+
+/// Mirror of `ckb_std::dynamic_loading::CKBDLContext`, nothing is loaded into the buffer
+pub struct CKBDLContext<T>(PhantomData<T>);
+
+impl<T> CKBDLContext<T> {
+    /// # Safety
+    ///
+    /// Same as `ckb_std::dynamic_loading::CKBDLContext::new`, the mock itself is always safe
+    pub unsafe fn new() -> Self {
+        CKBDLContext(PhantomData)
+    }
+}
+
+/// Prefilled data isn't required by the native secp256k1 library,
+/// the buffer passed to `load_from_cell_dep` is kept but not filled
+pub struct PrefilledData<'a>(&'a [u8]);
+
+impl<'a> PrefilledData<'a> {
+    pub fn load_from_cell_dep(
+        buf: &'a mut [u8; SECP256K1_DATA_SIZE],
+    ) -> Result<Self, Secp256k1Error> {
+        Ok(PrefilledData(&buf[..]))
+    }
+
+    /// The filled data
+    pub fn as_slice(&self) -> &[u8] {
+        self.0
+    }
+}
+
+/// Returns the message set by `set_sighash_all`, `group_source` is ignored
+pub fn sighash_all_message(_group_source: Source) -> Result<[u8; 32], Secp256k1Error> {
+    let (message, _signature) = sighash_all().ok_or(Secp256k1Error::Syscall)?;
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(message.as_slice());
+    Ok(buf)
+}
+
+/// Returns the message set by `set_sighash_all`, `group_source` and `keep_len` are ignored
+pub fn sighash_all_message_with_lock_prefix(
+    group_source: Source,
+    _keep_len: usize,
+) -> Result<[u8; 32], Secp256k1Error> {
+    sighash_all_message(group_source)
+}
+
+pub struct LibSecp256k1 {
+    /// cached prefilled data, see `recover_pubkey_cached`
+    pub(crate) prefilled_data: Option<PrefilledData<'static>>,
+}
+
+impl LibSecp256k1 {
+    pub fn load<T>(context: &mut CKBDLContext<T>) -> Self {
+        Self::try_load(context).expect("load secp256k1")
+    }
+
+    pub fn try_load<T>(_context: &mut CKBDLContext<T>) -> Result<Self, LoadError> {
+        Ok(LibSecp256k1 {
+            prefilled_data: None,
+        })
+    }
+
+    pub fn load_by_type_hash<T>(context: &mut CKBDLContext<T>, type_hash: &[u8; 32]) -> Self {
+        Self::try_load_by_type_hash(context, type_hash).expect("load secp256k1")
+    }
+
+    pub fn try_load_by_type_hash<T>(
+        context: &mut CKBDLContext<T>,
+        type_hash: &[u8; 32],
+    ) -> Result<Self, LoadError> {
+        Self::try_load_with(context, type_hash, HashType::Type)
+    }
+
+    pub fn load_with<T>(
+        context: &mut CKBDLContext<T>,
+        code_hash: &[u8; 32],
+        hash_type: HashType,
+    ) -> Self {
+        Self::try_load_with(context, code_hash, hash_type).expect("load secp256k1")
+    }
+
+    pub fn try_load_with<T>(
+        context: &mut CKBDLContext<T>,
+        _code_hash: &[u8; 32],
+        _hash_type: HashType,
+    ) -> Result<Self, LoadError> {
+        Self::try_load(context)
+    }
+
+    pub fn load_any<T>(context: &mut CKBDLContext<T>) -> (Self, &'static str) {
+        Self::try_load_any(context).expect("load secp256k1")
+    }
+
+    /// Returns the first known version in `CODE_HASHES_SECP256K1`
+    pub fn try_load_any<T>(
+        context: &mut CKBDLContext<T>,
+    ) -> Result<(Self, &'static str), LoadError> {
+        let (name, _code_hash) = CODE_HASHES_SECP256K1
+            .first()
+            .ok_or(LoadError::LibraryNotFound)?;
+        Ok((Self::try_load(context)?, *name))
+    }
+
+    pub fn validate_blake2b_sighash_all(
+        &self,
+        pubkey_hash: &mut [u8; 20],
+    ) -> Result<(), Secp256k1Error> {
+        let (message, signature) = sighash_all().ok_or(Secp256k1Error::Encoding)?;
+        let pubkey = self.recover_pubkey(&PrefilledData(&[]), &signature, &message)?;
+        pubkey_hash.copy_from_slice(&pubkey.blake160());
+        Ok(())
+    }
+
+    pub fn load_prefilled_data(&self) -> Result<PrefilledData<'static>, Secp256k1Error> {
+        Ok(PrefilledData(&[]))
+    }
+
+    pub fn recover_pubkey(
+        &self,
+        _prefilled_data: &PrefilledData,
+        signature: &RecoverableSignature,
+        message: &Message,
+    ) -> Result<Pubkey, Secp256k1Error> {
+        let recid = recovery::RecoveryId::from_i32(i32::from(signature.recovery_id()))
+            .map_err(|_| Secp256k1Error::ParseSignature)?;
+        let signature =
+            recovery::RecoverableSignature::from_compact(&signature.as_slice()[..64], recid)
+                .map_err(|_| Secp256k1Error::ParseSignature)?;
+        let message = secp256k1::Message::from_slice(message.as_slice())
+            .map_err(|_| Secp256k1Error::InvalidMessageSize)?;
+        let pubkey = Secp256k1::verification_only()
+            .recover(&message, &signature)
+            .map_err(|_| Secp256k1Error::RecoverPubkey)?;
+        Ok(Pubkey(pubkey.serialize()))
+    }
+}
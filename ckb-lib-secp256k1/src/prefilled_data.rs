@@ -0,0 +1,70 @@
+use crate::alloc::boxed::Box;
+use crate::error::Secp256k1Error;
+use crate::secp256k1_data::{CODE_HASH_SECP256K1_DATA, SECP256K1_DATA_SIZE};
+use ckb_std::{
+    ckb_constants::Source, error::SysError, high_level::find_cell_by_data_hash,
+    syscalls::load_cell_data,
+};
+
+enum Data<'a> {
+    Owned(Box<[u8; SECP256K1_DATA_SIZE]>),
+    Borrowed(&'a [u8; SECP256K1_DATA_SIZE]),
+}
+
+/// Prefilled data of secp256k1, either allocated on the heap by the library
+/// or loaded into a caller-provided buffer
+pub struct PrefilledData<'a> {
+    data: Data<'a>,
+    /// length of the filled data
+    len: usize,
+}
+
+impl<'a> PrefilledData<'a> {
+    /// Wrap the data filled by the library, `len` must equal to `SECP256K1_DATA_SIZE`
+    pub(crate) fn owned(
+        data: Box<[u8; SECP256K1_DATA_SIZE]>,
+        len: usize,
+    ) -> Result<Self, Secp256k1Error> {
+        if len != SECP256K1_DATA_SIZE {
+            return Err(Secp256k1Error::InvalidPrefilledDataSize);
+        }
+        Ok(PrefilledData {
+            data: Data::Owned(data),
+            len,
+        })
+    }
+
+    /// Load the `secp256k1_data` cell dep into `buf` without allocating,
+    /// `buf` can be a static buffer so the contract doesn't need a large heap
+    pub fn load_from_cell_dep(
+        buf: &'a mut [u8; SECP256K1_DATA_SIZE],
+    ) -> Result<Self, Secp256k1Error> {
+        let index = find_cell_by_data_hash(&CODE_HASH_SECP256K1_DATA, Source::CellDep)?
+            .ok_or(Secp256k1Error::DataCellNotFound)?;
+        let len = match load_cell_data(&mut buf[..], 0, index, Source::CellDep) {
+            Ok(len) => len,
+            Err(SysError::LengthNotEnough(len)) => len,
+            Err(err) => return Err(err.into()),
+        };
+        if len != SECP256K1_DATA_SIZE {
+            return Err(Secp256k1Error::InvalidPrefilledDataSize);
+        }
+        Ok(PrefilledData {
+            data: Data::Borrowed(buf),
+            len,
+        })
+    }
+
+    /// The filled data
+    pub fn as_slice(&self) -> &[u8] {
+        let data = match &self.data {
+            Data::Owned(data) => &data[..],
+            Data::Borrowed(data) => &data[..],
+        };
+        &data[..self.len]
+    }
+
+    pub(crate) fn as_ptr(&self) -> *const u8 {
+        self.as_slice().as_ptr()
+    }
+}
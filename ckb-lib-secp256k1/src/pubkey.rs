@@ -0,0 +1,90 @@
+use crate::error::Secp256k1Error;
+use crate::hash::{blake160, blake2b_256};
+use core::convert::TryFrom;
+#[cfg(feature = "uncompressed")]
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+/// Size of the compressed pubkey
+pub const PUBKEY_SIZE: usize = 33;
+/// Size of the uncompressed pubkey
+pub const UNCOMPRESSED_PUBKEY_SIZE: usize = 65;
+
+/// Compressed secp256k1 pubkey
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Pubkey(pub(crate) [u8; PUBKEY_SIZE]);
+
+impl Pubkey {
+    /// Parse a compressed pubkey with prefix 0x02/0x03, or an uncompressed pubkey with prefix 0x04,
+    /// fails if the pubkey isn't on the curve
+    #[cfg(feature = "uncompressed")]
+    pub fn from_slice(data: &[u8]) -> Result<Self, Secp256k1Error> {
+        match (data.len(), data.first()) {
+            (PUBKEY_SIZE, Some(0x02))
+            | (PUBKEY_SIZE, Some(0x03))
+            | (UNCOMPRESSED_PUBKEY_SIZE, Some(0x04)) => {
+                let point = parse_point(data)?.to_encoded_point(true);
+                let mut inner = [0u8; PUBKEY_SIZE];
+                inner.copy_from_slice(point.as_bytes());
+                Ok(Pubkey(inner))
+            }
+            _ => Err(Secp256k1Error::ParsePubkey),
+        }
+    }
+
+    /// Parse a compressed pubkey with prefix 0x02/0x03, only the length and the prefix are checked,
+    /// enable the `uncompressed` feature to validate the point and accept uncompressed pubkeys
+    #[cfg(not(feature = "uncompressed"))]
+    pub fn from_slice(data: &[u8]) -> Result<Self, Secp256k1Error> {
+        match (data.len(), data.first()) {
+            (PUBKEY_SIZE, Some(0x02)) | (PUBKEY_SIZE, Some(0x03)) => {
+                let mut inner = [0u8; PUBKEY_SIZE];
+                inner.copy_from_slice(data);
+                Ok(Pubkey(inner))
+            }
+            _ => Err(Secp256k1Error::ParsePubkey),
+        }
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Uncompressed form of the pubkey: 0x04 | x | y
+    #[cfg(feature = "uncompressed")]
+    pub fn to_uncompressed(&self) -> Result<[u8; UNCOMPRESSED_PUBKEY_SIZE], Secp256k1Error> {
+        let point = parse_point(&self.0)?.to_encoded_point(false);
+        let mut buf = [0u8; UNCOMPRESSED_PUBKEY_SIZE];
+        buf.copy_from_slice(point.as_bytes());
+        Ok(buf)
+    }
+
+    /// CKB blake2b hash of the compressed pubkey
+    pub fn blake2b_256(&self) -> [u8; 32] {
+        blake2b_256(&self.0)
+    }
+
+    /// The first 20 bytes of `blake2b_256`, as used in the secp256k1 lock args
+    pub fn blake160(&self) -> [u8; 20] {
+        blake160(&self.0)
+    }
+}
+
+impl TryFrom<&[u8]> for Pubkey {
+    type Error = Secp256k1Error;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        Pubkey::from_slice(data)
+    }
+}
+
+impl From<Pubkey> for [u8; PUBKEY_SIZE] {
+    fn from(pubkey: Pubkey) -> Self {
+        pubkey.0
+    }
+}
+
+/// Parse the point on the curve, fails if the pubkey isn't on the curve
+#[cfg(feature = "uncompressed")]
+fn parse_point(data: &[u8]) -> Result<k256::PublicKey, Secp256k1Error> {
+    k256::PublicKey::from_sec1_bytes(data).map_err(|_| Secp256k1Error::ParsePubkey)
+}
@@ -0,0 +1,8 @@
+/// Size of the secp256k1 prefilled data
+pub const SECP256K1_DATA_SIZE: usize = 1048576;
+
+/// Data hash of the `secp256k1_data` cell
+pub const CODE_HASH_SECP256K1_DATA: [u8; 32] = [
+    151, 153, 190, 226, 81, 185, 117, 184, 44, 69, 160, 33, 84, 206, 40, 206, 200, 156, 88, 83,
+    236, 193, 77, 18, 183, 184, 204, 207, 193, 158, 10, 244,
+];
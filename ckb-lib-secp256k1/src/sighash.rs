@@ -0,0 +1,112 @@
+use crate::alloc::{vec, vec::Vec};
+use crate::error::Secp256k1Error;
+use crate::hash::new_blake2b;
+use blake2b_ref::Blake2b;
+use ckb_std::{
+    ckb_constants::Source,
+    ckb_types::{packed::WitnessArgsReader, prelude::*},
+    error::SysError,
+    high_level::{load_input_since, load_tx_hash},
+    syscalls::load_witness,
+};
+
+const BUF_SIZE: usize = 1024;
+
+/// Compute the sighash-all message of the script group, same as the system secp256k1 lock:
+///
+/// blake2b(tx_hash | first witness of the group with `lock` zeroed | other witnesses of the group
+/// | witnesses without corresponding inputs), each witness is prefixed by its length as u64 little endian.
+///
+/// `group_source` is usually `Source::GroupInput`.
+pub fn sighash_all_message(group_source: Source) -> Result<[u8; 32], Secp256k1Error> {
+    sighash_all_message_with_lock_prefix(group_source, 0)
+}
+
+/// Same as `sighash_all_message`, except the first `keep_len` bytes of `lock` are kept in the digest,
+/// e.g. the multisig script of the system `secp256k1_blake160_multisig_all` lock,
+/// which only zeroes the signatures after the script.
+pub fn sighash_all_message_with_lock_prefix(
+    group_source: Source,
+    keep_len: usize,
+) -> Result<[u8; 32], Secp256k1Error> {
+    let tx_hash = load_tx_hash()?;
+    let mut blake2b = new_blake2b();
+    blake2b.update(&tx_hash);
+
+    // digest the first witness with lock zeroed after `keep_len`
+    let mut witness = load_witness_data(0, group_source)?;
+    zero_lock(&mut witness, keep_len)?;
+    update_witness(&mut blake2b, &witness);
+
+    // digest other witnesses in the group
+    digest_witnesses(&mut blake2b, 1, group_source)?;
+
+    // digest witnesses without corresponding inputs
+    let inputs_len = inputs_len()?;
+    digest_witnesses(&mut blake2b, inputs_len, Source::Input)?;
+
+    let mut message = [0u8; 32];
+    blake2b.finalize(&mut message);
+    Ok(message)
+}
+
+/// Count the inputs of the transaction, unlike `QueryIter` it returns other syscall errors instead of panicking
+fn inputs_len() -> Result<usize, SysError> {
+    let mut len = 0;
+    loop {
+        match load_input_since(len, Source::Input) {
+            Ok(_) => len += 1,
+            Err(SysError::IndexOutOfBound) => return Ok(len),
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+fn digest_witnesses(blake2b: &mut Blake2b, start: usize, source: Source) -> Result<(), SysError> {
+    for i in start.. {
+        match load_witness_data(i, source) {
+            Ok(witness) => update_witness(blake2b, &witness),
+            Err(SysError::IndexOutOfBound) => break,
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+fn update_witness(blake2b: &mut Blake2b, witness: &[u8]) {
+    blake2b.update(&(witness.len() as u64).to_le_bytes());
+    blake2b.update(witness);
+}
+
+fn load_witness_data(index: usize, source: Source) -> Result<Vec<u8>, SysError> {
+    let mut buf = [0u8; BUF_SIZE];
+    match load_witness(&mut buf, 0, index, source) {
+        Ok(len) => Ok(buf[..len].to_vec()),
+        Err(SysError::LengthNotEnough(len)) => {
+            let mut data = vec![0u8; len];
+            load_witness(&mut data, 0, index, source)?;
+            Ok(data)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Zero the `lock` field of the `WitnessArgs` in place, except its first `keep_len` bytes
+fn zero_lock(witness: &mut [u8], keep_len: usize) -> Result<(), Secp256k1Error> {
+    WitnessArgsReader::verify(witness, false).map_err(|_| Secp256k1Error::Encoding)?;
+    // the table header is the total size followed by the offsets of the fields,
+    // `lock` is the first field, a present lock is a 4-bytes length followed by the data
+    let field_offset = |i: usize| {
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(&witness[4 + i * 4..8 + i * 4]);
+        u32::from_le_bytes(buf) as usize
+    };
+    let (start, end) = (field_offset(0), field_offset(1));
+    if end > start {
+        let zero_start = end.min(start + 4 + keep_len);
+        for b in &mut witness[zero_start..end] {
+            *b = 0;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use crate::error::Secp256k1Error;
+use core::convert::TryFrom;
+
+/// Size of the recoverable signature
+pub const SIGNATURE_SIZE: usize = 65;
+/// Size of the message
+pub const MESSAGE_SIZE: usize = 32;
+
+/// Recoverable signature: r | s | recovery id
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct RecoverableSignature([u8; SIGNATURE_SIZE]);
+
+impl RecoverableSignature {
+    /// Parse a 65-bytes signature, the recovery id must be in range 0..4
+    pub fn from_slice(data: &[u8]) -> Result<Self, Secp256k1Error> {
+        if data.len() != SIGNATURE_SIZE {
+            return Err(Secp256k1Error::InvalidSignatureSize);
+        }
+        if data[SIGNATURE_SIZE - 1] >= 4 {
+            return Err(Secp256k1Error::ParseSignature);
+        }
+        let mut inner = [0u8; SIGNATURE_SIZE];
+        inner.copy_from_slice(data);
+        Ok(RecoverableSignature(inner))
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Recovery id of the signature
+    pub fn recovery_id(&self) -> u8 {
+        self.0[SIGNATURE_SIZE - 1]
+    }
+}
+
+impl TryFrom<&[u8]> for RecoverableSignature {
+    type Error = Secp256k1Error;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        RecoverableSignature::from_slice(data)
+    }
+}
+
+/// 32-bytes message to sign
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Message([u8; MESSAGE_SIZE]);
+
+impl Message {
+    pub fn from_slice(data: &[u8]) -> Result<Self, Secp256k1Error> {
+        if data.len() != MESSAGE_SIZE {
+            return Err(Secp256k1Error::InvalidMessageSize);
+        }
+        let mut inner = [0u8; MESSAGE_SIZE];
+        inner.copy_from_slice(data);
+        Ok(Message(inner))
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl TryFrom<&[u8]> for Message {
+    type Error = Secp256k1Error;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        Message::from_slice(data)
+    }
+}
+
+impl From<[u8; MESSAGE_SIZE]> for Message {
+    fn from(inner: [u8; MESSAGE_SIZE]) -> Self {
+        Message(inner)
+    }
+}
@@ -0,0 +1,64 @@
+use crate::error::Secp256k1Error;
+use crate::hasher::PubkeyHasher;
+use crate::pubkey::Pubkey;
+use crate::signature::{Message, RecoverableSignature};
+use crate::{LibSecp256k1, PrefilledData};
+
+/// The pubkey expected to sign the message
+#[derive(Debug, Clone, Copy)]
+pub enum ExpectedPubkey<'a> {
+    /// Compressed pubkey
+    Pubkey(&'a Pubkey),
+    /// Blake160 hash of the compressed pubkey
+    Blake160(&'a [u8; 20]),
+}
+
+impl<'a> From<&'a Pubkey> for ExpectedPubkey<'a> {
+    fn from(pubkey: &'a Pubkey) -> Self {
+        ExpectedPubkey::Pubkey(pubkey)
+    }
+}
+
+impl<'a> From<&'a [u8; 20]> for ExpectedPubkey<'a> {
+    fn from(pubkey_hash: &'a [u8; 20]) -> Self {
+        ExpectedPubkey::Blake160(pubkey_hash)
+    }
+}
+
+impl LibSecp256k1 {
+    /// Recover pubkey from the signature and compare it with the expected one,
+    /// returns `Secp256k1Error::PubkeyMismatch` if they are different
+    pub fn verify<'a, E: Into<ExpectedPubkey<'a>>>(
+        &self,
+        prefilled_data: &PrefilledData,
+        signature: &RecoverableSignature,
+        message: &Message,
+        expected: E,
+    ) -> Result<(), Secp256k1Error> {
+        let pubkey = self.recover_pubkey(prefilled_data, signature, message)?;
+        let matched = match expected.into() {
+            ExpectedPubkey::Pubkey(expected) => pubkey.as_slice() == expected.as_slice(),
+            ExpectedPubkey::Blake160(expected) => &pubkey.blake160() == expected,
+        };
+        if !matched {
+            return Err(Secp256k1Error::PubkeyMismatch);
+        }
+        Ok(())
+    }
+
+    /// Recover pubkey from the signature and compare its hash by `H` with `expected_hash`,
+    /// returns `Secp256k1Error::PubkeyMismatch` if they are different
+    pub fn verify_with<H: PubkeyHasher>(
+        &self,
+        prefilled_data: &PrefilledData,
+        signature: &RecoverableSignature,
+        message: &Message,
+        expected_hash: &[u8],
+    ) -> Result<(), Secp256k1Error> {
+        let pubkey = self.recover_pubkey(prefilled_data, signature, message)?;
+        if H::hash(&pubkey)?.as_ref() != expected_hash {
+            return Err(Secp256k1Error::PubkeyMismatch);
+        }
+        Ok(())
+    }
+}
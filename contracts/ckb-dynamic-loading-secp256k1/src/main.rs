@@ -15,20 +15,30 @@ use core::result::Result;
 // https://nervosnetwork.github.io/ckb-std/riscv64imac-unknown-none-elf/doc/ckb_std/index.html
 use ckb_std::{
     ckb_constants::Source,
-    ckb_types::{bytes::Bytes, prelude::*},
+    ckb_types::{bytes::Bytes, packed::WitnessArgsReader, prelude::*},
     debug, default_alloc,
     dynamic_loading::CKBDLContext,
     entry,
     error::SysError,
-    high_level::{load_script, load_witness_args},
+    high_level::{load_script, load_witness, load_witness_args},
 };
 
-use blake2b_ref::{Blake2b, Blake2bBuilder};
-use ckb_lib_secp256k1::LibSecp256k1;
+use ckb_lib_secp256k1::{
+    btc_signed_message, eth_personal_message, sighash_all_message_with_lock_prefix, Blake160,
+    Hash160, KeccakAddress, LibSecp256k1, Message, PrefilledData, PubkeyHasher,
+    RecoverableSignature, Secp256k1Error, SECP256K1_DATA_SIZE, SIGNATURE_SIZE,
+};
+use types::{Error, SecpWitnessReader, SecpWitnessUnionReader};
+
+mod multisig;
+mod since;
 
 entry!(entry);
-// Alloc 4K fast HEAP + 2M HEAP to receives PrefilledData
-default_alloc!(4 * 1024, 2048 * 1024, 64);
+// Alloc 4K fast HEAP + 64K HEAP, PrefilledData is loaded into a static buffer
+default_alloc!(4 * 1024, 64 * 1024, 64);
+
+/// Buffer to receive PrefilledData
+static mut SECP256K1_DATA: [u8; SECP256K1_DATA_SIZE] = [0u8; SECP256K1_DATA_SIZE];
 
 /// Program entry
 fn entry() -> i8 {
@@ -39,47 +49,14 @@ fn entry() -> i8 {
     }
 }
 
-/// Error
-#[repr(i8)]
-enum Error {
-    IndexOutOfBound = 1,
-    ItemMissing,
-    LengthNotEnough,
-    Encoding,
-    // Add customized errors here...
-    Secp256k1,
-    WrongPubkey,
-    LoadPrefilledData,
-    RecoverPubkey,
-}
-
-impl From<SysError> for Error {
-    fn from(err: SysError) -> Self {
-        use SysError::*;
-        match err {
-            IndexOutOfBound => Self::IndexOutOfBound,
-            ItemMissing => Self::ItemMissing,
-            LengthNotEnough(_) => Self::LengthNotEnough,
-            Encoding => Self::Encoding,
-            Unknown(err_code) => panic!("unexpected sys error {}", err_code),
-        }
-    }
-}
-
-fn new_blake2b() -> Blake2b {
-    Blake2bBuilder::new(32)
-        .personal(b"ckb-default-hash")
-        .build()
-}
-
 fn test_validate_blake2b_sighash_all(
     lib: &LibSecp256k1,
     expected_pubkey_hash: &[u8],
 ) -> Result<(), Error> {
     let mut pubkey_hash = [0u8; 20];
     lib.validate_blake2b_sighash_all(&mut pubkey_hash)
-        .map_err(|err_code| {
-            debug!("secp256k1 error {}", err_code);
+        .map_err(|err| {
+            debug!("secp256k1 error {:?}", err);
             Error::Secp256k1
         })?;
 
@@ -90,55 +67,205 @@ fn test_validate_blake2b_sighash_all(
     Ok(())
 }
 
+/// Args is the hash of `hash_size` bytes, optionally followed by the minimum since of the group inputs,
+/// returns the hash once the since is satisfied
+fn check_args_since(args: &[u8], hash_size: usize) -> Result<&[u8], Error> {
+    let (hash, since) = since::split_args(args, hash_size)?;
+    if let Some(since) = since {
+        since::check_since(since)?;
+    }
+    Ok(hash)
+}
+
+/// The sighash-all message of the script group, the first `keep_len` bytes of `lock` are not zeroed
+fn load_message(keep_len: usize) -> Result<Message, Error> {
+    let message =
+        sighash_all_message_with_lock_prefix(Source::GroupInput, keep_len).map_err(|err| {
+            debug!("sighash all error: {:?}", err);
+            Error::SighashAll
+        })?;
+    Ok(message.into())
+}
+
+/// Load the prefilled data into `buf`, the static buffer taken by `main`
+fn load_prefilled_data(
+    buf: &'static mut [u8; SECP256K1_DATA_SIZE],
+) -> Result<PrefilledData<'static>, Error> {
+    PrefilledData::load_from_cell_dep(buf).map_err(|err| {
+        debug!("load prefilled data error: {:?}", err);
+        Error::LoadPrefilledData
+    })
+}
+
+/// Recover the pubkey from the signature in `lock`, its hash by `H` must equal the hash in `args`.
+/// The signature is parsed by `parse_signature`, the signed message is derived from the sighash-all
+/// message by `to_message`, so the signature is bound to the transaction
+fn test_verify_with<H: PubkeyHasher>(
+    lib: &LibSecp256k1,
+    secp256k1_data: &'static mut [u8; SECP256K1_DATA_SIZE],
+    args: &[u8],
+    lock: &[u8],
+    parse_signature: fn(&[u8]) -> Result<RecoverableSignature, Secp256k1Error>,
+    to_message: fn(&Message) -> Message,
+) -> Result<(), Error> {
+    let expected_hash = check_args_since(args, H::hash_size())?;
+    if lock.len() != SIGNATURE_SIZE {
+        return Err(Error::WitnessLength);
+    }
+    let signature = parse_signature(lock).map_err(|err| {
+        debug!("parse signature error: {:?}", err);
+        Error::Encoding
+    })?;
+    let message = to_message(&load_message(0)?);
+
+    // recover pubkey and compare with expected_hash
+    let prefilled_data = load_prefilled_data(secp256k1_data)?;
+    lib.verify_with::<H>(&prefilled_data, &signature, &message, expected_hash)
+        .map_err(|err| {
+            debug!("verify error: {:?}", err);
+            match err {
+                Secp256k1Error::PubkeyMismatch => Error::WrongPubkey,
+                _ => Error::RecoverPubkey,
+            }
+        })
+}
+
+/// Recover mode: `lock` is the signature of the sighash-all message, args is the blake160 of pubkey
+fn test_recover_pubkey(
+    lib: &LibSecp256k1,
+    secp256k1_data: &'static mut [u8; SECP256K1_DATA_SIZE],
+    args: &[u8],
+    lock: &[u8],
+) -> Result<(), Error> {
+    test_verify_with::<Blake160>(
+        lib,
+        secp256k1_data,
+        args,
+        lock,
+        RecoverableSignature::from_slice,
+        |message| *message,
+    )
+}
+
+/// Ethereum mode: `lock` is the `personal_sign` signature of the sighash-all message,
+/// args is the ethereum address
+fn test_eth_address(
+    lib: &LibSecp256k1,
+    secp256k1_data: &'static mut [u8; SECP256K1_DATA_SIZE],
+    args: &[u8],
+    lock: &[u8],
+) -> Result<(), Error> {
+    test_verify_with::<KeccakAddress>(
+        lib,
+        secp256k1_data,
+        args,
+        lock,
+        RecoverableSignature::from_eth_slice,
+        eth_personal_message,
+    )
+}
+
+/// Bitcoin mode: `lock` is the bitcoin `signmessage` signature of the hex string of the sighash-all
+/// message, args is the hash160 of the compressed pubkey
+fn test_btc_hash160(
+    lib: &LibSecp256k1,
+    secp256k1_data: &'static mut [u8; SECP256K1_DATA_SIZE],
+    args: &[u8],
+    lock: &[u8],
+) -> Result<(), Error> {
+    test_verify_with::<Hash160>(
+        lib,
+        secp256k1_data,
+        args,
+        lock,
+        RecoverableSignature::from_btc_slice,
+        btc_signed_message,
+    )
+}
+
+/// Additional witnesses of the script group must be absent, empty, or a `WitnessArgs` without `lock`,
+/// only the first witness carries the signatures, which cover the whole group
+fn check_group_witnesses() -> Result<(), Error> {
+    for index in 1.. {
+        let witness = match load_witness(index, Source::GroupInput) {
+            Ok(witness) => witness,
+            Err(SysError::IndexOutOfBound) => break,
+            Err(err) => return Err(err.into()),
+        };
+        if witness.is_empty() {
+            continue;
+        }
+        let witness_args = WitnessArgsReader::from_slice(&witness).map_err(|err| {
+            debug!("parse group witness {} error: {:?}", index, err);
+            Error::Encoding
+        })?;
+        if witness_args.lock().to_opt().is_some() {
+            return Err(Error::GroupWitness);
+        }
+    }
+    Ok(())
+}
+
 fn main() -> Result<(), Error> {
     let script = load_script()?;
     let args: Bytes = script.args().unpack();
 
-    if args.len() != 20 {
-        return Err(Error::Encoding);
-    }
-
     let witness_args = load_witness_args(0, Source::GroupInput)?;
 
     // create a DL context with 128K buffer size
-    let mut context = unsafe{ CKBDLContext::<[u8; 128 * 1024]>::new()};
-    let lib = LibSecp256k1::load(&mut context);
-
-    if witness_args.input_type().to_opt().is_none() {
-        test_validate_blake2b_sighash_all(&lib, &args)?;
-    } else {
-        let witness: Bytes = witness_args
-            .input_type()
-            .to_opt()
-            .ok_or(Error::Encoding)?
-            .unpack();
-        let mut message = [0u8; 32];
-        let mut signature = [0u8; 65];
-        let msg_len = message.len();
-        let sig_len = signature.len();
-        assert_eq!(witness.len(), message.len() + signature.len());
-        message.copy_from_slice(&witness[..msg_len]);
-        signature.copy_from_slice(&witness[msg_len..msg_len + sig_len]);
-        // recover pubkey_hash
-        let prefilled_data = lib.load_prefilled_data().map_err(|err| {
-            debug!("load prefilled data error: {}", err);
-            Error::LoadPrefilledData
-        })?;
-        let pubkey = lib
-            .recover_pubkey(&prefilled_data, &signature, &message)
-            .map_err(|err| {
-                debug!("recover pubkey error: {}", err);
-                Error::RecoverPubkey
-            })?;
-        let pubkey_hash = {
-            let mut buf = [0u8; 32];
-            let mut hasher = new_blake2b();
-            hasher.update(pubkey.as_slice());
-            hasher.finalize(&mut buf);
-            buf
-        };
-        if &args[..] != &pubkey_hash[..20] {
-            return Err(Error::WrongPubkey);
+    let mut context = unsafe { CKBDLContext::<[u8; 128 * 1024]>::new() };
+    let (mut lib, version) = LibSecp256k1::try_load_any(&mut context).map_err(|err| {
+        debug!("load secp256k1 error: {:?}", err);
+        Error::LoadLibrary
+    })?;
+    debug!("loaded secp256k1 {}", version);
+    // the only reference to the static buffer, `main` runs once so it's never aliased
+    let secp256k1_data = unsafe { &mut SECP256K1_DATA };
+
+    // `input_type` selects the mode, the signatures are in `lock`.
+    // Without `input_type` it's the witness of a system lock, the sighash-all mode or the multisig mode
+    let input_type: Option<Bytes> = witness_args
+        .input_type()
+        .to_opt()
+        .map(|input_type| input_type.unpack());
+    let secp_witness = match &input_type {
+        Some(input_type) => Some(SecpWitnessReader::from_slice(input_type).map_err(|err| {
+            debug!("parse witness error: {:?}", err);
+            Error::Encoding
+        })?),
+        None => None,
+    };
+    let lock: Bytes = witness_args
+        .lock()
+        .to_opt()
+        .ok_or(Error::WitnessLength)?
+        .unpack();
+    match secp_witness.map(|secp_witness| secp_witness.to_enum()) {
+        // the witness of the system multisig lock, its lock is the multisig script and signatures,
+        // which is never a single signature
+        None if lock.len() != SIGNATURE_SIZE => {
+            check_group_witnesses()?;
+            multisig::verify(&mut lib, secp256k1_data, &args, &lock)?;
+        }
+        None | Some(SecpWitnessUnionReader::SighashAll(_)) => {
+            let pubkey_hash = check_args_since(&args, 20)?;
+            test_validate_blake2b_sighash_all(&lib, pubkey_hash)?;
+        }
+        Some(SecpWitnessUnionReader::Recover(_)) => {
+            check_group_witnesses()?;
+            test_recover_pubkey(&lib, secp256k1_data, &args, &lock)?;
+        }
+        Some(SecpWitnessUnionReader::Multisig(_)) => {
+            check_group_witnesses()?;
+            multisig::verify(&mut lib, secp256k1_data, &args, &lock)?;
+        }
+        Some(SecpWitnessUnionReader::Eth(_)) => {
+            check_group_witnesses()?;
+            test_eth_address(&lib, secp256k1_data, &args, &lock)?;
+        }
+        Some(SecpWitnessUnionReader::Btc(_)) => {
+            check_group_witnesses()?;
+            test_btc_hash160(&lib, secp256k1_data, &args, &lock)?;
         }
     }
 
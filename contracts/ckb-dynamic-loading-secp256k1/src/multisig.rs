@@ -0,0 +1,98 @@
+//! M-of-N multisig, compatible with the system `secp256k1_blake160_multisig_all` lock
+//!
+//! * args: blake160(multisig script) | since (optional, u64 little endian)
+//! * witness lock: multisig script | signatures, `input_type` is `Multisig` or absent
+//! * multisig script: reserved (0) | require_first_n | threshold | pubkeys count | blake160 of pubkeys
+
+use crate::{
+    load_message, load_prefilled_data,
+    since::{check_since, split_args},
+    Error,
+};
+use ckb_lib_secp256k1::{
+    hash::blake160, LibSecp256k1, RecoverableSignature, SECP256K1_DATA_SIZE, SIGNATURE_SIZE,
+};
+use ckb_std::debug;
+
+const FLAGS_SIZE: usize = 4;
+const BLAKE160_SIZE: usize = 20;
+
+pub fn verify(
+    lib: &mut LibSecp256k1,
+    secp256k1_data: &'static mut [u8; SECP256K1_DATA_SIZE],
+    args: &[u8],
+    lock: &[u8],
+) -> Result<(), Error> {
+    let (script_hash, since) = split_args(args, BLAKE160_SIZE)?;
+    if lock.len() < FLAGS_SIZE {
+        return Err(Error::WitnessLength);
+    }
+    let reserved = lock[0];
+    let require_first_n = lock[1] as usize;
+    let threshold = lock[2] as usize;
+    let pubkeys_cnt = lock[3] as usize;
+    if reserved != 0 {
+        return Err(Error::InvalidReserveField);
+    }
+    if pubkeys_cnt == 0 {
+        return Err(Error::InvalidPubkeysCnt);
+    }
+    if threshold > pubkeys_cnt || threshold == 0 {
+        return Err(Error::InvalidThreshold);
+    }
+    if require_first_n > threshold {
+        return Err(Error::InvalidRequireFirstN);
+    }
+    let script_size = FLAGS_SIZE + BLAKE160_SIZE * pubkeys_cnt;
+    if lock.len() != script_size + SIGNATURE_SIZE * threshold {
+        return Err(Error::WitnessLength);
+    }
+
+    // check multisig script hash
+    let (script, signatures) = lock.split_at(script_size);
+    if &blake160(script)[..] != script_hash {
+        return Err(Error::MultisigScriptHash);
+    }
+
+    // check since
+    if let Some(since) = since {
+        check_since(since)?;
+    }
+
+    // same as the system multisig lock, the multisig script is signed,
+    // only the signatures are zeroed
+    let message = load_message(script_size)?;
+    // the prefilled data is shared by all signatures
+    lib.set_prefilled_data(load_prefilled_data(secp256k1_data)?);
+
+    // each signature must match an unused pubkey
+    let pubkey_hashes = &script[FLAGS_SIZE..];
+    let mut used = [false; 256];
+    for signature in signatures.chunks(SIGNATURE_SIZE) {
+        let signature = RecoverableSignature::from_slice(signature).map_err(|err| {
+            debug!("parse signature error: {:?}", err);
+            Error::Encoding
+        })?;
+        let pubkey_hash = lib
+            .recover_pubkey_cached(&signature, &message)
+            .map_err(|err| {
+                debug!("recover pubkey error: {:?}", err);
+                Error::RecoverPubkey
+            })?
+            .blake160();
+        let matched = pubkey_hashes
+            .chunks(BLAKE160_SIZE)
+            .enumerate()
+            .position(|(i, hash)| !used[i] && hash == &pubkey_hash[..]);
+        match matched {
+            Some(i) => used[i] = true,
+            None => return Err(Error::WrongPubkey),
+        }
+    }
+
+    // the first n pubkeys must sign
+    if used[..require_first_n].iter().any(|used| !used) {
+        return Err(Error::WrongPubkey);
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use crate::Error;
+use ckb_std::{ckb_constants::Source, error::SysError, high_level::load_input_since};
+
+/// The 8 most significant bits of since are flags
+const SINCE_VALUE_BITS: u64 = 56;
+const SINCE_VALUE_MASK: u64 = 0x00ff_ffff_ffff_ffff;
+/// Absolute epoch number with fraction
+const SINCE_EPOCH_FRACTION_FLAG: u8 = 0b0010_0000;
+/// Size of the optional since in args
+const SINCE_SIZE: usize = 8;
+
+/// Split args into the hash of `hash_size` bytes and the optional since (u64 little endian) after it
+pub fn split_args(args: &[u8], hash_size: usize) -> Result<(&[u8], Option<u64>), Error> {
+    if args.len() == hash_size {
+        return Ok((args, None));
+    }
+    if args.len() != hash_size + SINCE_SIZE {
+        return Err(Error::ArgsLength);
+    }
+    let (hash, since_bytes) = args.split_at(hash_size);
+    let mut since = [0u8; SINCE_SIZE];
+    since.copy_from_slice(since_bytes);
+    Ok((hash, Some(u64::from_le_bytes(since))))
+}
+
+/// Check the `since` of every input in the script group satisfies the minimum `since`,
+/// same as the system multisig lock: the flags must be equal and the value must not be less.
+pub fn check_since(since: u64) -> Result<(), Error> {
+    let since_flag = (since >> SINCE_VALUE_BITS) as u8;
+    let since_value = since & SINCE_VALUE_MASK;
+    // QueryIter panics on errors other than IndexOutOfBound, iterate the inputs explicitly
+    for index in 0.. {
+        let input_since = match load_input_since(index, Source::GroupInput) {
+            Ok(input_since) => input_since,
+            Err(SysError::IndexOutOfBound) => break,
+            Err(err) => return Err(err.into()),
+        };
+        let input_since_flag = (input_since >> SINCE_VALUE_BITS) as u8;
+        let input_since_value = input_since & SINCE_VALUE_MASK;
+        if since_flag != input_since_flag {
+            return Err(Error::IncorrectSinceFlags);
+        }
+        let satisfied = if since_flag == SINCE_EPOCH_FRACTION_FLAG {
+            epoch_number_with_fraction_cmp(input_since_value, since_value) >= 0
+        } else {
+            input_since_value >= since_value
+        };
+        if !satisfied {
+            return Err(Error::IncorrectSinceValue);
+        }
+    }
+    Ok(())
+}
+
+/// Compare two epoch numbers with fraction: number (24 bits) | index (16 bits) | length (16 bits)
+fn epoch_number_with_fraction_cmp(a: u64, b: u64) -> i8 {
+    const NUMBER_MASK: u64 = (1 << 24) - 1;
+    const INDEX_OFFSET: u64 = 24;
+    const INDEX_MASK: u64 = (1 << 16) - 1;
+    const LENGTH_OFFSET: u64 = 40;
+    const LENGTH_MASK: u64 = (1 << 16) - 1;
+
+    let a_epoch = a & NUMBER_MASK;
+    let a_index = (a >> INDEX_OFFSET) & INDEX_MASK;
+    let a_len = (a >> LENGTH_OFFSET) & LENGTH_MASK;
+    let b_epoch = b & NUMBER_MASK;
+    let b_index = (b >> INDEX_OFFSET) & INDEX_MASK;
+    let b_len = (b >> LENGTH_OFFSET) & LENGTH_MASK;
+
+    if a_epoch != b_epoch {
+        return if a_epoch < b_epoch { -1 } else { 1 };
+    }
+    // in the same epoch, compare a_index / a_len with b_index / b_len
+    let a_block = a_index * b_len;
+    let b_block = b_index * a_len;
+    if a_block < b_block {
+        -1
+    } else if a_block > b_block {
+        1
+    } else {
+        0
+    }
+}
@@ -0,0 +1,108 @@
+//! Test script of the library loaders, the args select the loader:
+//!
+//! * empty: `try_load_any`
+//! * code hash (32 bytes) | hash type (1 byte, 0 is data, 1 is type): `try_load_with`
+
+#![no_std]
+#![no_main]
+#![feature(lang_items)]
+#![feature(alloc_error_handler)]
+#![feature(panic_info_message)]
+
+use core::result::Result;
+
+use ckb_std::{
+    ckb_types::{bytes::Bytes, prelude::*},
+    default_alloc,
+    dynamic_loading::CKBDLContext,
+    entry,
+    error::SysError,
+    high_level::load_script,
+};
+
+use ckb_lib_secp256k1::{HashType, LibSecp256k1, LoadError};
+
+entry!(entry);
+default_alloc!(4 * 1024, 64 * 1024, 64);
+
+/// Program entry
+fn entry() -> i8 {
+    // Call main function and return error code
+    match main() {
+        Ok(_) => 0,
+        Err(err) => err as i8,
+    }
+}
+
+/// Error, the discriminant is the exit code of the script
+#[repr(i8)]
+enum Error {
+    /// Syscall: index out of bound
+    IndexOutOfBound = 1,
+    /// Syscall: item missing
+    ItemMissing = 2,
+    /// Syscall: buffer length not enough
+    LengthNotEnough = 3,
+    /// Syscall: data encoding error, or malformed args
+    Encoding = 4,
+    /// LoadError::LibraryNotFound
+    LibraryNotFound = 5,
+    /// LoadError::ContextTooSmall
+    ContextTooSmall = 6,
+    /// LoadError::InvalidLibrary
+    InvalidLibrary = 7,
+    /// LoadError::Syscall, or an unknown syscall error
+    Syscall = 8,
+    /// LoadError::SymbolMissing
+    SymbolMissing = 9,
+}
+
+impl From<SysError> for Error {
+    fn from(err: SysError) -> Self {
+        use SysError::*;
+        match err {
+            IndexOutOfBound => Self::IndexOutOfBound,
+            ItemMissing => Self::ItemMissing,
+            LengthNotEnough(_) => Self::LengthNotEnough,
+            Encoding => Self::Encoding,
+            Unknown(_) => Self::Syscall,
+        }
+    }
+}
+
+impl From<LoadError> for Error {
+    fn from(err: LoadError) -> Self {
+        match err {
+            LoadError::LibraryNotFound => Self::LibraryNotFound,
+            LoadError::ContextTooSmall => Self::ContextTooSmall,
+            LoadError::InvalidLibrary => Self::InvalidLibrary,
+            LoadError::Syscall => Self::Syscall,
+            LoadError::SymbolMissing(_) => Self::SymbolMissing,
+        }
+    }
+}
+
+fn main() -> Result<(), Error> {
+    let script = load_script()?;
+    let args: Bytes = script.args().unpack();
+
+    // create a DL context with 128K buffer size
+    let mut context = unsafe { CKBDLContext::<[u8; 128 * 1024]>::new() };
+    if args.is_empty() {
+        LibSecp256k1::try_load_any(&mut context)?;
+        return Ok(());
+    }
+
+    if args.len() != 33 {
+        return Err(Error::Encoding);
+    }
+    let mut code_hash = [0u8; 32];
+    code_hash.copy_from_slice(&args[..32]);
+    let hash_type = match args[32] {
+        0 => HashType::Data,
+        1 => HashType::Type,
+        _ => return Err(Error::Encoding),
+    };
+    LibSecp256k1::try_load_with(&mut context, &code_hash, hash_type)?;
+    Ok(())
+}
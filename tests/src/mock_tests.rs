@@ -0,0 +1,405 @@
+use ckb_lib_secp256k1::{
+    btc_message_hash, btc_signed_message, eth_personal_message, mock, sighash_all_message,
+    Blake160, CKBDLContext, ExpectedPubkey, Hash160, KeccakAddress, LibSecp256k1, Message, Pubkey,
+    RecoverableSignature, Secp256k1Error, Source, CODE_HASHES_SECP256K1,
+};
+use ckb_tool::ckb_crypto::secp::Generator;
+use ckb_tool::ckb_hash::blake2b_256;
+use ckb_tool::ckb_types::H256;
+use rand::{thread_rng, Rng};
+
+fn load_lib() -> LibSecp256k1 {
+    let mut context = unsafe { CKBDLContext::<[u8; 128 * 1024]>::new() };
+    LibSecp256k1::load(&mut context)
+}
+
+#[test]
+fn test_mock_recover_pubkey() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+
+    let mut rng = thread_rng();
+    let mut message = [0u8; 32];
+    rng.fill(&mut message);
+    let sig = privkey.sign_recoverable(&message.into()).expect("sign");
+
+    let lib = load_lib();
+    let prefilled_data = lib.load_prefilled_data().expect("load prefilled data");
+    let signature = RecoverableSignature::from_slice(&sig.serialize()).expect("signature");
+    let recovered = lib
+        .recover_pubkey(&prefilled_data, &signature, &message.into())
+        .expect("recover pubkey");
+    assert_eq!(recovered.as_slice(), &pubkey.serialize()[..]);
+
+    assert_eq!(
+        RecoverableSignature::from_slice(&sig.serialize()[..64]),
+        Err(Secp256k1Error::InvalidSignatureSize)
+    );
+    assert_eq!(
+        Message::from_slice(&message[..31]),
+        Err(Secp256k1Error::InvalidMessageSize)
+    );
+}
+
+#[test]
+fn test_mock_validate_blake2b_sighash_all() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+
+    let mut rng = thread_rng();
+    let mut message = [0u8; 32];
+    rng.fill(&mut message);
+    let sig = privkey.sign_recoverable(&message.into()).expect("sign");
+    let signature = RecoverableSignature::from_slice(&sig.serialize()).expect("signature");
+
+    let lib = load_lib();
+    let mut pubkey_hash = [0u8; 20];
+    assert_eq!(
+        lib.validate_blake2b_sighash_all(&mut pubkey_hash),
+        Err(Secp256k1Error::Encoding)
+    );
+
+    mock::set_sighash_all(message.into(), signature);
+    assert_eq!(sighash_all_message(Source::GroupInput), Ok(message));
+    lib.validate_blake2b_sighash_all(&mut pubkey_hash)
+        .expect("validate");
+    assert_eq!(&pubkey_hash[..], &blake2b_256(&pubkey.serialize())[..20]);
+    mock::clear_sighash_all();
+}
+
+#[test]
+fn test_mock_load_any() {
+    let mut context = unsafe { CKBDLContext::<[u8; 128 * 1024]>::new() };
+    let (_lib, name) = LibSecp256k1::try_load_any(&mut context).expect("load secp256k1");
+    assert_eq!(name, CODE_HASHES_SECP256K1[0].0);
+}
+
+#[test]
+fn test_pubkey_encoding() {
+    let privkey = Generator::random_privkey();
+    let serialized = privkey.pubkey().expect("pubkey").serialize();
+    let pubkey = Pubkey::from_slice(&serialized).expect("compressed pubkey");
+    assert_eq!(pubkey.as_slice(), &serialized[..]);
+
+    let uncompressed = pubkey.to_uncompressed().expect("uncompressed pubkey");
+    assert_eq!(uncompressed[0], 0x04);
+    assert_eq!(Pubkey::from_slice(&uncompressed), Ok(pubkey));
+
+    let mut invalid_prefix = serialized.clone();
+    invalid_prefix[0] = 0x04;
+    assert_eq!(
+        Pubkey::from_slice(&invalid_prefix),
+        Err(Secp256k1Error::ParsePubkey)
+    );
+    assert_eq!(
+        Pubkey::from_slice(&serialized[..32]),
+        Err(Secp256k1Error::ParsePubkey)
+    );
+
+    // x = 5 isn't on the curve, 5^3 + 7 is not a square modulo p
+    let mut invalid_x = [0u8; 33];
+    invalid_x[0] = 0x02;
+    invalid_x[32] = 5;
+    assert_eq!(
+        Pubkey::from_slice(&invalid_x),
+        Err(Secp256k1Error::ParsePubkey)
+    );
+    // x is greater than p
+    let mut overflow_x = [0xffu8; 33];
+    overflow_x[0] = 0x03;
+    assert_eq!(
+        Pubkey::from_slice(&overflow_x),
+        Err(Secp256k1Error::ParsePubkey)
+    );
+}
+
+#[test]
+fn test_mock_recover_pubkey_cached() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+
+    let mut lib = load_lib();
+    let mut rng = thread_rng();
+    for _ in 0..3 {
+        let mut message = [0u8; 32];
+        rng.fill(&mut message);
+        let sig = privkey.sign_recoverable(&message.into()).expect("sign");
+        let signature = RecoverableSignature::from_slice(&sig.serialize()).expect("signature");
+        let recovered = lib
+            .recover_pubkey_cached(&signature, &message.into())
+            .expect("recover pubkey");
+        assert_eq!(recovered.as_slice(), &pubkey.serialize()[..]);
+    }
+    lib.drop_prefilled_data();
+}
+
+#[test]
+fn test_mock_recover_pubkeys() {
+    let privkeys: Vec<_> = (0..3).map(|_| Generator::random_privkey()).collect();
+    let mut rng = thread_rng();
+    let mut items: Vec<_> = privkeys
+        .iter()
+        .map(|privkey| {
+            let mut message = [0u8; 32];
+            rng.fill(&mut message);
+            let sig = privkey.sign_recoverable(&message.into()).expect("sign");
+            let signature = RecoverableSignature::from_slice(&sig.serialize()).expect("signature");
+            (signature, Message::from(message))
+        })
+        .collect();
+
+    let lib = load_lib();
+    let prefilled_data = lib.load_prefilled_data().expect("load prefilled data");
+    let pubkeys = lib
+        .recover_pubkeys(&prefilled_data, &items)
+        .expect("recover pubkeys");
+    let mut output = [None; 3];
+    lib.recover_pubkeys_into(&prefilled_data, &items, &mut output)
+        .expect("recover pubkeys");
+    for ((privkey, pubkey), output) in privkeys.iter().zip(pubkeys).zip(output.iter()) {
+        let expected = privkey.pubkey().expect("pubkey").serialize();
+        assert_eq!(pubkey.as_slice(), &expected[..]);
+        assert_eq!(output, &Some(pubkey));
+    }
+
+    let err = lib
+        .recover_pubkeys_into(&prefilled_data, &items, &mut output[..2])
+        .expect_err("output too short");
+    assert_eq!(err.index, 2);
+    assert_eq!(err.error, Secp256k1Error::InvalidOutputSize);
+
+    // an invalid signature fails the batch at its index
+    items[1].0 = RecoverableSignature::from_slice(&[0u8; 65]).expect("signature");
+    let err = lib
+        .recover_pubkeys(&prefilled_data, &items)
+        .expect_err("invalid signature");
+    assert_eq!(err.index, 1);
+}
+
+#[test]
+fn test_eth_address() {
+    // pubkey of the private key 1
+    let pubkey = Pubkey::from_slice(&[
+        0x02, 0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac, 0x55, 0xa0, 0x62, 0x95, 0xce, 0x87,
+        0x0b, 0x07, 0x02, 0x9b, 0xfc, 0xdb, 0x2d, 0xce, 0x28, 0xd9, 0x59, 0xf2, 0x81, 0x5b, 0x16,
+        0xf8, 0x17, 0x98,
+    ])
+    .expect("pubkey");
+    assert_eq!(
+        pubkey.eth_address().expect("eth address"),
+        [
+            0x7e, 0x5f, 0x45, 0x52, 0x09, 0x1a, 0x69, 0x12, 0x5d, 0x5d, 0xfc, 0xb7, 0xb8, 0xc2,
+            0x65, 0x90, 0x29, 0x39, 0x5b, 0xdf
+        ]
+    );
+}
+
+#[test]
+fn test_mock_recover_eth_signature() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+
+    let mut rng = thread_rng();
+    let mut message = [0u8; 32];
+    rng.fill(&mut message);
+    let personal_message = eth_personal_message(&message.into());
+    let mut sig = privkey
+        .sign_recoverable(&H256::from_slice(personal_message.as_slice()).expect("message"))
+        .expect("sign")
+        .serialize();
+    sig[64] += 27;
+
+    let lib = load_lib();
+    let prefilled_data = lib.load_prefilled_data().expect("load prefilled data");
+    let signature = RecoverableSignature::from_eth_slice(&sig).expect("signature");
+    let recovered = lib
+        .recover_pubkey(&prefilled_data, &signature, &personal_message)
+        .expect("recover pubkey");
+    assert_eq!(recovered.as_slice(), &pubkey.serialize()[..]);
+
+    sig[64] = 29;
+    assert_eq!(
+        RecoverableSignature::from_eth_slice(&sig),
+        Err(Secp256k1Error::ParseSignature)
+    );
+}
+
+#[test]
+fn test_hash160() {
+    // pubkey of the private key 1
+    let pubkey = Pubkey::from_slice(&[
+        0x02, 0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac, 0x55, 0xa0, 0x62, 0x95, 0xce, 0x87,
+        0x0b, 0x07, 0x02, 0x9b, 0xfc, 0xdb, 0x2d, 0xce, 0x28, 0xd9, 0x59, 0xf2, 0x81, 0x5b, 0x16,
+        0xf8, 0x17, 0x98,
+    ])
+    .expect("pubkey");
+    assert_eq!(
+        pubkey.hash160(),
+        [
+            0x75, 0x1e, 0x76, 0xe8, 0x19, 0x91, 0x96, 0xd4, 0x54, 0x94, 0x1c, 0x45, 0xd1, 0xb3,
+            0xa3, 0x23, 0xf1, 0x43, 0x3b, 0xd6
+        ]
+    );
+}
+
+#[test]
+fn test_mock_recover_btc_signature() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+
+    let mut rng = thread_rng();
+    let mut message = [0u8; 32];
+    rng.fill(&mut message);
+    let signed_message = btc_signed_message(&message.into());
+    let sig = privkey
+        .sign_recoverable(&H256::from_slice(signed_message.as_slice()).expect("message"))
+        .expect("sign")
+        .serialize();
+    // header | r | s, the header of a compressed pubkey is 31 + recovery id
+    let mut btc_sig = vec![31 + sig[64]];
+    btc_sig.extend_from_slice(&sig[..64]);
+
+    let lib = load_lib();
+    let prefilled_data = lib.load_prefilled_data().expect("load prefilled data");
+    let signature = RecoverableSignature::from_btc_slice(&btc_sig).expect("signature");
+    let recovered = lib
+        .recover_pubkey(&prefilled_data, &signature, &signed_message)
+        .expect("recover pubkey");
+    assert_eq!(recovered.as_slice(), &pubkey.serialize()[..]);
+
+    // uncompressed pubkey
+    btc_sig[0] = 27;
+    assert_eq!(
+        RecoverableSignature::from_btc_slice(&btc_sig),
+        Err(Secp256k1Error::ParseSignature)
+    );
+}
+
+#[test]
+fn test_btc_signmessage_vector() {
+    // signature of `signmessage` from the rust-bitcoin test vectors
+    let signature = RecoverableSignature::from_btc_slice(&[
+        0x20, 0x03, 0x36, 0xa9, 0x7d, 0xb8, 0xb5, 0x8c, 0x7f, 0x6d, 0xd0, 0x53, 0x22, 0x05, 0x4b,
+        0x84, 0x3f, 0x10, 0x10, 0x08, 0xeb, 0x3e, 0x52, 0x69, 0x9a, 0x30, 0x78, 0x9d, 0x91, 0xdd,
+        0x45, 0x81, 0x88, 0x05, 0xae, 0x03, 0x99, 0x5b, 0xa5, 0x01, 0xcc, 0x23, 0x3e, 0x75, 0x9e,
+        0xe9, 0x0e, 0x62, 0x13, 0x05, 0xc7, 0xe8, 0x5d, 0x29, 0x50, 0x22, 0x4f, 0xfd, 0x97, 0x87,
+        0x3d, 0x64, 0xb5, 0x87, 0x5a,
+    ])
+    .expect("signature");
+    let pubkey = Pubkey::from_slice(&[
+        0x03, 0x51, 0x53, 0x7c, 0xc1, 0x27, 0xb4, 0xfa, 0x40, 0xb7, 0x2d, 0xea, 0x90, 0x4a, 0x34,
+        0xab, 0x60, 0xdc, 0xd4, 0x51, 0x32, 0x70, 0x8d, 0x74, 0x6b, 0x78, 0xe6, 0xc0, 0x41, 0x72,
+        0xf9, 0x0a, 0xfa,
+    ])
+    .expect("pubkey");
+    let message = btc_message_hash(b"rust-bitcoin MessageSignature test");
+
+    let lib = load_lib();
+    let prefilled_data = lib.load_prefilled_data().expect("load prefilled data");
+    let recovered = lib
+        .recover_pubkey(&prefilled_data, &signature, &message)
+        .expect("recover pubkey");
+    assert_eq!(recovered, pubkey);
+
+    // the signed text of a message is its lowercase hex string
+    let mut message = [0u8; 32];
+    for (i, byte) in message.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+    assert_eq!(
+        btc_signed_message(&message.into()),
+        btc_message_hash(b"000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f")
+    );
+    assert_eq!(
+        btc_signed_message(&message.into()).as_slice(),
+        &[
+            0xab, 0x88, 0x15, 0xa3, 0xe9, 0xc1, 0xd8, 0xa1, 0xa2, 0x75, 0xf4, 0xee, 0x69, 0xcf,
+            0xf7, 0xe5, 0xbe, 0x37, 0x49, 0x82, 0x81, 0x67, 0xc2, 0x74, 0x86, 0xbb, 0x48, 0xe0,
+            0xe9, 0x1e, 0x20, 0xb3
+        ][..]
+    );
+}
+
+#[test]
+fn test_mock_verify() {
+    let privkey = Generator::random_privkey();
+    let pubkey =
+        Pubkey::from_slice(&privkey.pubkey().expect("pubkey").serialize()).expect("pubkey");
+    let other_pubkey = Pubkey::from_slice(
+        &Generator::random_privkey()
+            .pubkey()
+            .expect("pubkey")
+            .serialize(),
+    )
+    .expect("pubkey");
+
+    let mut rng = thread_rng();
+    let mut message = [0u8; 32];
+    rng.fill(&mut message);
+    let sig = privkey.sign_recoverable(&message.into()).expect("sign");
+    let signature = RecoverableSignature::from_slice(&sig.serialize()).expect("signature");
+    let message = message.into();
+
+    let lib = load_lib();
+    let prefilled_data = lib.load_prefilled_data().expect("load prefilled data");
+    lib.verify(&prefilled_data, &signature, &message, &pubkey)
+        .expect("verify pubkey");
+    lib.verify(&prefilled_data, &signature, &message, &pubkey.blake160())
+        .expect("verify blake160");
+    lib.verify(
+        &prefilled_data,
+        &signature,
+        &message,
+        ExpectedPubkey::Blake160(&pubkey.blake160()),
+    )
+    .expect("verify blake160");
+
+    // signed by another key
+    assert_eq!(
+        lib.verify(&prefilled_data, &signature, &message, &other_pubkey),
+        Err(Secp256k1Error::PubkeyMismatch)
+    );
+    assert_eq!(
+        lib.verify(
+            &prefilled_data,
+            &signature,
+            &message,
+            &other_pubkey.blake160()
+        ),
+        Err(Secp256k1Error::PubkeyMismatch)
+    );
+}
+
+#[test]
+fn test_mock_verify_with() {
+    let privkey = Generator::random_privkey();
+    let pubkey =
+        Pubkey::from_slice(&privkey.pubkey().expect("pubkey").serialize()).expect("pubkey");
+
+    let mut rng = thread_rng();
+    let mut message = [0u8; 32];
+    rng.fill(&mut message);
+    let sig = privkey.sign_recoverable(&message.into()).expect("sign");
+    let signature = RecoverableSignature::from_slice(&sig.serialize()).expect("signature");
+    let message = message.into();
+
+    let lib = load_lib();
+    let prefilled_data = lib.load_prefilled_data().expect("load prefilled data");
+    lib.verify_with::<Blake160>(&prefilled_data, &signature, &message, &pubkey.blake160())
+        .expect("verify blake160");
+    lib.verify_with::<KeccakAddress>(
+        &prefilled_data,
+        &signature,
+        &message,
+        &pubkey.eth_address().expect("eth address"),
+    )
+    .expect("verify keccak address");
+    lib.verify_with::<Hash160>(&prefilled_data, &signature, &message, &pubkey.hash160())
+        .expect("verify hash160");
+
+    // the hash of another scheme doesn't match
+    assert_eq!(
+        lib.verify_with::<Hash160>(&prefilled_data, &signature, &message, &pubkey.blake160()),
+        Err(Secp256k1Error::PubkeyMismatch)
+    );
+}
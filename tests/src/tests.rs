@@ -1,49 +1,74 @@
 use super::*;
+use ckb_lib_secp256k1::{
+    btc_signed_message, eth_personal_message, hash, Message, Pubkey, CODE_HASH_SECP256K1,
+};
 use ckb_system_scripts::BUNDLED_CELL;
 use ckb_testtool::context::Context;
 use ckb_tool::ckb_crypto::secp::{Generator, Privkey};
 use ckb_tool::ckb_error::assert_error_eq;
-use ckb_tool::ckb_hash::{blake2b_256, new_blake2b};
+use ckb_tool::ckb_hash::new_blake2b;
 use ckb_tool::ckb_script::ScriptError;
 use ckb_tool::ckb_types::{
     bytes::Bytes,
-    core::{TransactionBuilder, TransactionView},
+    core::{ScriptHashType, TransactionBuilder, TransactionView},
     packed::{self, *},
     prelude::*,
     H256,
 };
 use rand::{thread_rng, Rng};
+use std::convert::TryFrom;
 use std::fs;
+use types::{
+    Btc, Error as ExitCode, Eth, Multisig, Recover, SecpWitness, SecpWitnessUnion, SighashAll,
+};
 
 const MAX_CYCLES: u64 = 10_000_000;
+const SIGNATURE_SIZE: usize = 65;
 
-fn blake160(data: &[u8]) -> [u8; 20] {
-    let mut buf = [0u8; 20];
-    let hash = blake2b_256(data);
-    buf.clone_from_slice(&hash[..20]);
-    buf
+fn blake160(pubkey: &[u8]) -> [u8; 20] {
+    Pubkey::from_slice(pubkey).expect("pubkey").blake160()
 }
 
 fn sign_tx(tx: TransactionView, key: &Privkey) -> TransactionView {
-    const SIGNATURE_SIZE: usize = 65;
+    sign_tx_with_witness(tx, key, witness_with_mode(SighashAll::default()))
+}
+
+/// Witness whose `input_type` selects the mode
+fn witness_with_mode<T: Into<SecpWitnessUnion>>(mode: T) -> WitnessArgs {
+    let secp_witness = SecpWitness::new_builder().set(mode).build();
+    WitnessArgs::new_builder()
+        .input_type(Some(Bytes::from(secp_witness.as_slice().to_vec())).pack())
+        .build()
+}
+
+/// Sign the sighash-all message and put the signature into the `lock` of `witness`
+fn sign_tx_with_witness(
+    tx: TransactionView,
+    key: &Privkey,
+    witness: WitnessArgs,
+) -> TransactionView {
+    let message = sighash_all(&tx, &witness, &[0u8; SIGNATURE_SIZE]);
+    let sig = key.sign_recoverable(&message).expect("sign");
+    let witness = witness
+        .as_builder()
+        .lock(Some(Bytes::from(sig.serialize())).pack())
+        .build();
+    set_first_witness(tx, witness)
+}
 
+/// Compute the sighash-all message with `witness` as the first witness,
+/// its lock is replaced by `lock`, i.e. the lock with the signatures zeroed
+fn sighash_all(tx: &TransactionView, witness: &WitnessArgs, lock: &[u8]) -> H256 {
     let witnesses_len = tx.witnesses().len();
     let tx_hash = tx.hash();
-    let mut signed_witnesses: Vec<packed::Bytes> = Vec::new();
     let mut blake2b = new_blake2b();
     let mut message = [0u8; 32];
     blake2b.update(&tx_hash.raw_data());
     // digest the first witness
-    let witness = WitnessArgs::default();
-    let zero_lock: Bytes = {
-        let mut buf = Vec::new();
-        buf.resize(SIGNATURE_SIZE, 0);
-        buf.into()
-    };
     let witness_for_digest = witness
         .clone()
         .as_builder()
-        .lock(Some(zero_lock).pack())
+        .lock(Some(Bytes::from(lock.to_vec())).pack())
         .build();
     let witness_len = witness_for_digest.as_bytes().len() as u64;
     blake2b.update(&witness_len.to_le_bytes());
@@ -55,16 +80,13 @@ fn sign_tx(tx: TransactionView, key: &Privkey) -> TransactionView {
         blake2b.update(&witness.raw_data());
     });
     blake2b.finalize(&mut message);
-    let message = H256::from(message);
-    let sig = key.sign_recoverable(&message).expect("sign");
-    signed_witnesses.push(
-        witness
-            .as_builder()
-            .lock(Some(Bytes::from(sig.serialize())).pack())
-            .build()
-            .as_bytes()
-            .pack(),
-    );
+    H256::from(message)
+}
+
+/// Replace the first witness of the transaction
+fn set_first_witness(tx: TransactionView, witness: WitnessArgs) -> TransactionView {
+    let witnesses_len = tx.witnesses().len();
+    let mut signed_witnesses: Vec<packed::Bytes> = vec![witness.as_bytes().pack()];
     for i in 1..witnesses_len {
         signed_witnesses.push(tx.witnesses().get(i).unwrap());
     }
@@ -73,94 +95,147 @@ fn sign_tx(tx: TransactionView, key: &Privkey) -> TransactionView {
         .build()
 }
 
-#[test]
-fn test_basic() {
-    // generate key pair
-    let privkey = Generator::random_privkey();
-    let pubkey = privkey.pubkey().expect("pubkey");
-    let pubkey_hash = blake160(&pubkey.serialize());
-
-    // deploy contract
-    let mut context = Context::default();
-    let contract_bin: Bytes = Loader::default().load_binary("ckb-dynamic-loading-secp256k1");
-    let out_point = context.deploy_cell(contract_bin);
-
-    let secp256k1_bin: Bytes =
-        fs::read("../ckb-miscellaneous-scripts/build/secp256k1_blake2b_sighash_all_dual")
-            .expect("load secp256k1")
-            .into();
-    let secp256k1_out_point = context.deploy_cell(secp256k1_bin);
-    let secp256k1_dep = CellDep::new_builder()
-        .out_point(secp256k1_out_point)
+/// Sign in ethereum mode: `personal_sign` of the sighash-all message, v is 27 + recovery id
+fn eth_sign_tx(tx: TransactionView, key: &Privkey) -> TransactionView {
+    let witness = witness_with_mode(Eth::default());
+    let message = sighash_all(&tx, &witness, &[0u8; SIGNATURE_SIZE]);
+    let message = eth_personal_message(&Message::from_slice(message.as_bytes()).expect("message"));
+    let mut sig = key
+        .sign_recoverable(&H256::from_slice(message.as_slice()).expect("message"))
+        .expect("sign")
+        .serialize();
+    sig[SIGNATURE_SIZE - 1] += 27;
+    let witness = witness
+        .as_builder()
+        .lock(Some(Bytes::from(sig)).pack())
         .build();
+    set_first_witness(tx, witness)
+}
 
-    let secp256k1_data_bin = BUNDLED_CELL.get("specs/cells/secp256k1_data").unwrap();
-    let secp256k1_data_out_point = context.deploy_cell(secp256k1_data_bin.to_vec().into());
-    let secp256k1_data_dep = CellDep::new_builder()
-        .out_point(secp256k1_data_out_point)
+/// Sign in bitcoin mode: `signmessage` signature of the hex string of the sighash-all message, header | r | s
+fn btc_sign_tx(tx: TransactionView, key: &Privkey) -> TransactionView {
+    let witness = witness_with_mode(Btc::default());
+    let message = sighash_all(&tx, &witness, &[0u8; SIGNATURE_SIZE]);
+    let message = btc_signed_message(&Message::from_slice(message.as_bytes()).expect("message"));
+    let sig = key
+        .sign_recoverable(&H256::from_slice(message.as_slice()).expect("message"))
+        .expect("sign")
+        .serialize();
+    // the header of a compressed pubkey is 31 + recovery id
+    let mut lock = vec![31 + sig[SIGNATURE_SIZE - 1]];
+    lock.extend_from_slice(&sig[..SIGNATURE_SIZE - 1]);
+    let witness = witness
+        .as_builder()
+        .lock(Some(Bytes::from(lock)).pack())
         .build();
+    set_first_witness(tx, witness)
+}
 
-    // prepare scripts
-    let lock_script = context
-        .build_script(&out_point, pubkey_hash.to_vec().into())
-        .expect("script");
-    let lock_script_dep = CellDep::new_builder().out_point(out_point).build();
+/// Set the `since` of every input
+fn set_inputs_since(tx: TransactionView, since: u64) -> TransactionView {
+    let inputs: Vec<CellInput> = tx
+        .inputs()
+        .into_iter()
+        .map(|input| input.as_builder().since(since.pack()).build())
+        .collect();
+    tx.as_advanced_builder().set_inputs(inputs).build()
+}
 
-    // prepare cells
+/// Args of the pubkey hash or the multisig script hash with the minimum since of the group inputs
+fn args_with_since(hash: &[u8], since: u64) -> Bytes {
+    let mut args = hash.to_vec();
+    args.extend_from_slice(&since.to_le_bytes());
+    args.into()
+}
+
+/// Add an input locked by the same script as the first output, `witness` is at its index
+fn add_group_input(context: &mut Context, tx: TransactionView, witness: Bytes) -> TransactionView {
+    let lock_script = tx.output(0).expect("output").lock();
     let input_out_point = context.create_cell(
         CellOutput::new_builder()
             .capacity(1000u64.pack())
-            .lock(lock_script.clone())
+            .lock(lock_script)
             .build(),
         Bytes::new(),
     );
     let input = CellInput::new_builder()
         .previous_output(input_out_point)
         .build();
-    let outputs = vec![
-        CellOutput::new_builder()
-            .capacity(500u64.pack())
-            .lock(lock_script.clone())
-            .build(),
-        CellOutput::new_builder()
-            .capacity(500u64.pack())
-            .lock(lock_script)
-            .build(),
-    ];
+    let mut witnesses: Vec<packed::Bytes> = tx.witnesses().into_iter().collect();
+    witnesses.resize(tx.inputs().len(), Bytes::new().pack());
+    witnesses.push(witness.pack());
+    tx.as_advanced_builder()
+        .input(input)
+        .set_witnesses(witnesses)
+        .build()
+}
 
-    let outputs_data = vec![Bytes::new(); 2];
+/// Append a witness without corresponding input, it's covered by the sighash-all message
+fn push_extra_witness(tx: TransactionView, witness: Bytes) -> TransactionView {
+    let mut witnesses: Vec<packed::Bytes> = tx.witnesses().into_iter().collect();
+    witnesses.resize(tx.inputs().len(), Bytes::new().pack());
+    witnesses.push(witness.pack());
+    tx.as_advanced_builder().set_witnesses(witnesses).build()
+}
 
-    // build transaction
-    let tx = TransactionBuilder::default()
-        .input(input)
-        .outputs(outputs)
-        .outputs_data(outputs_data.pack())
-        .cell_dep(lock_script_dep)
-        .cell_dep(secp256k1_dep)
-        .cell_dep(secp256k1_data_dep)
-        .build();
-    let tx = context.complete_tx(tx);
+/// Random private keys of the multisig and the blake160 of their pubkeys
+fn multisig_keys(n: usize) -> (Vec<Privkey>, Vec<[u8; 20]>) {
+    let privkeys: Vec<_> = (0..n).map(|_| Generator::random_privkey()).collect();
+    let pubkey_hashes = privkeys
+        .iter()
+        .map(|privkey| blake160(&privkey.pubkey().expect("pubkey").serialize()))
+        .collect();
+    (privkeys, pubkey_hashes)
+}
 
-    // sign
-    let tx = sign_tx(tx, &privkey);
+/// Multisig script: reserved | require_first_n | threshold | pubkeys count | pubkey hashes
+fn multisig_script(require_first_n: u8, threshold: u8, pubkey_hashes: &[[u8; 20]]) -> Vec<u8> {
+    let mut script = vec![0u8, require_first_n, threshold, pubkey_hashes.len() as u8];
+    for pubkey_hash in pubkey_hashes {
+        script.extend_from_slice(pubkey_hash);
+    }
+    script
+}
 
-    // run
-    let cycles = context
-        .verify_tx(&tx, MAX_CYCLES)
-        .expect("pass verification");
-    println!("consume cycles: {}", cycles);
+/// Sign in multisig mode, the lock is the multisig script followed by the signatures
+fn multisig_sign_tx(
+    tx: TransactionView,
+    keys: &[&Privkey],
+    multisig_script: &[u8],
+) -> TransactionView {
+    multisig_sign_tx_with_witness(
+        tx,
+        keys,
+        multisig_script,
+        witness_with_mode(Multisig::default()),
+    )
 }
 
-#[test]
-fn test_sign_with_wrong_key() {
-    // generate key pair
-    let privkey = Generator::random_privkey();
-    let pubkey = privkey.pubkey().expect("pubkey");
-    let pubkey_hash = blake160(&pubkey.serialize());
-    let wrong_privkey = Generator::random_privkey();
+/// Sign the sighash-all message by `keys`, put the multisig script and the signatures into the `lock` of `witness`
+fn multisig_sign_tx_with_witness(
+    tx: TransactionView,
+    keys: &[&Privkey],
+    multisig_script: &[u8],
+    witness: WitnessArgs,
+) -> TransactionView {
+    // same as the system multisig lock, the multisig script is signed and the signatures are zeroed
+    let mut zeroed_lock = multisig_script.to_vec();
+    zeroed_lock.resize(multisig_script.len() + SIGNATURE_SIZE * keys.len(), 0);
+    let message = sighash_all(&tx, &witness, &zeroed_lock);
+    let mut lock = multisig_script.to_vec();
+    for key in keys {
+        let sig = key.sign_recoverable(&message).expect("sign");
+        lock.extend_from_slice(&sig.serialize());
+    }
+    let witness = witness
+        .as_builder()
+        .lock(Some(Bytes::from(lock)).pack())
+        .build();
+    set_first_witness(tx, witness)
+}
 
-    // deploy contract
-    let mut context = Context::default();
+/// Deploy the contract and the secp256k1 cells, build a transaction spending a cell locked by `lock_args`
+fn build_test_tx(context: &mut Context, lock_args: Bytes) -> TransactionView {
     let contract_bin: Bytes = Loader::default().load_binary("ckb-dynamic-loading-secp256k1");
     let out_point = context.deploy_cell(contract_bin);
 
@@ -180,9 +255,7 @@ fn test_sign_with_wrong_key() {
         .build();
 
     // prepare scripts
-    let lock_script = context
-        .build_script(&out_point, pubkey_hash.to_vec().into())
-        .expect("script");
+    let lock_script = context.build_script(&out_point, lock_args).expect("script");
     let lock_script_dep = CellDep::new_builder().out_point(out_point).build();
 
     // prepare cells
@@ -218,7 +291,39 @@ fn test_sign_with_wrong_key() {
         .cell_dep(secp256k1_dep)
         .cell_dep(secp256k1_data_dep)
         .build();
-    let tx = context.complete_tx(tx);
+    context.complete_tx(tx)
+}
+
+#[test]
+fn test_basic() {
+    // generate key pair
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let pubkey_hash = blake160(&pubkey.serialize());
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, pubkey_hash.to_vec().into());
+
+    // sign
+    let tx = sign_tx(tx, &privkey);
+
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_sign_with_wrong_key() {
+    // generate key pair
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let pubkey_hash = blake160(&pubkey.serialize());
+    let wrong_privkey = Generator::random_privkey();
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, pubkey_hash.to_vec().into());
 
     // sign
     let tx = sign_tx(tx, &wrong_privkey);
@@ -237,82 +342,12 @@ fn test_recover_pubkey() {
     let pubkey = privkey.pubkey().expect("pubkey");
     let pubkey_hash = blake160(&pubkey.serialize());
 
-    // deploy contract
     let mut context = Context::default();
-    let contract_bin: Bytes = Loader::default().load_binary("ckb-dynamic-loading-secp256k1");
-    let out_point = context.deploy_cell(contract_bin);
-
-    let secp256k1_bin: Bytes =
-        fs::read("../ckb-miscellaneous-scripts/build/secp256k1_blake2b_sighash_all_dual")
-            .expect("load secp256k1")
-            .into();
-    let secp256k1_out_point = context.deploy_cell(secp256k1_bin);
-    let secp256k1_dep = CellDep::new_builder()
-        .out_point(secp256k1_out_point)
-        .build();
-
-    let secp256k1_data_bin = BUNDLED_CELL.get("specs/cells/secp256k1_data").unwrap();
-    let secp256k1_data_out_point = context.deploy_cell(secp256k1_data_bin.to_vec().into());
-    let secp256k1_data_dep = CellDep::new_builder()
-        .out_point(secp256k1_data_out_point)
-        .build();
-
-    // prepare scripts
-    let lock_script = context
-        .build_script(&out_point, pubkey_hash.to_vec().into())
-        .expect("script");
-    let lock_script_dep = CellDep::new_builder().out_point(out_point).build();
-
-    // prepare cells
-    let input_out_point = context.create_cell(
-        CellOutput::new_builder()
-            .capacity(1000u64.pack())
-            .lock(lock_script.clone())
-            .build(),
-        Bytes::new(),
-    );
-    let input = CellInput::new_builder()
-        .previous_output(input_out_point)
-        .build();
-    let outputs = vec![
-        CellOutput::new_builder()
-            .capacity(500u64.pack())
-            .lock(lock_script.clone())
-            .build(),
-        CellOutput::new_builder()
-            .capacity(500u64.pack())
-            .lock(lock_script)
-            .build(),
-    ];
-
-    let outputs_data = vec![Bytes::new(); 2];
-
-    let mut rng = thread_rng();
-    let mut message = [0u8; 32];
-    rng.fill(&mut message);
-    let sig = privkey.sign_recoverable(&message.into()).expect("sign");
-    let witness = {
-        let mut args = Vec::new();
-        args.extend_from_slice(&message);
-        args.extend_from_slice(&sig.serialize());
-        WitnessArgs::new_builder()
-            .input_type(Some(Bytes::from(args)).pack())
-            .build()
-            .as_bytes()
-            .pack()
-    };
+    let tx = build_test_tx(&mut context, pubkey_hash.to_vec().into());
 
-    // build transaction
-    let tx = TransactionBuilder::default()
-        .input(input)
-        .outputs(outputs)
-        .outputs_data(outputs_data.pack())
-        .cell_dep(lock_script_dep)
-        .cell_dep(secp256k1_dep)
-        .cell_dep(secp256k1_data_dep)
-        .witness(witness)
-        .build();
-    let tx = context.complete_tx(tx);
+    // sign in recover mode
+    let witness = witness_with_mode(Recover::default());
+    let tx = sign_tx_with_witness(tx, &privkey, witness);
 
     // run
     let cycles = context
@@ -329,84 +364,43 @@ fn test_recover_pubkey_with_wrong_signature() {
     let pubkey = privkey.pubkey().expect("pubkey");
     let pubkey_hash = blake160(&pubkey.serialize());
 
-    // deploy contract
     let mut context = Context::default();
-    let contract_bin: Bytes = Loader::default().load_binary("ckb-dynamic-loading-secp256k1");
-    let out_point = context.deploy_cell(contract_bin);
-
-    let secp256k1_bin: Bytes =
-        fs::read("../ckb-miscellaneous-scripts/build/secp256k1_blake2b_sighash_all_dual")
-            .expect("load secp256k1")
-            .into();
-    let secp256k1_out_point = context.deploy_cell(secp256k1_bin);
-    let secp256k1_dep = CellDep::new_builder()
-        .out_point(secp256k1_out_point)
-        .build();
+    let tx = build_test_tx(&mut context, pubkey_hash.to_vec().into());
 
-    let secp256k1_data_bin = BUNDLED_CELL.get("specs/cells/secp256k1_data").unwrap();
-    let secp256k1_data_out_point = context.deploy_cell(secp256k1_data_bin.to_vec().into());
-    let secp256k1_data_dep = CellDep::new_builder()
-        .out_point(secp256k1_data_out_point)
-        .build();
+    // sign in recover mode
+    let witness = witness_with_mode(Recover::default());
+    let tx = sign_tx_with_witness(tx, &wrong_privkey, witness);
 
-    // prepare scripts
-    let lock_script = context
-        .build_script(&out_point, pubkey_hash.to_vec().into())
-        .expect("script");
-    let lock_script_dep = CellDep::new_builder().out_point(out_point).build();
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(6),);
+}
 
-    // prepare cells
-    let input_out_point = context.create_cell(
-        CellOutput::new_builder()
-            .capacity(1000u64.pack())
-            .lock(lock_script.clone())
-            .build(),
-        Bytes::new(),
-    );
-    let input = CellInput::new_builder()
-        .previous_output(input_out_point)
-        .build();
-    let outputs = vec![
-        CellOutput::new_builder()
-            .capacity(500u64.pack())
-            .lock(lock_script.clone())
-            .build(),
-        CellOutput::new_builder()
-            .capacity(500u64.pack())
-            .lock(lock_script)
-            .build(),
-    ];
+#[test]
+fn test_recover_pubkey_with_unbound_message() {
+    // generate key pair
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let pubkey_hash = blake160(&pubkey.serialize());
 
-    let outputs_data = vec![Bytes::new(); 2];
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, pubkey_hash.to_vec().into());
 
+    // the owner's signature of a message other than the transaction
     let mut rng = thread_rng();
     let mut message = [0u8; 32];
     rng.fill(&mut message);
-    let sig = wrong_privkey
-        .sign_recoverable(&message.into())
-        .expect("sign");
-    let witness = {
-        let mut args = Vec::new();
-        args.extend_from_slice(&message);
-        args.extend_from_slice(&sig.serialize());
-        WitnessArgs::new_builder()
-            .input_type(Some(Bytes::from(args)).pack())
-            .build()
-            .as_bytes()
-            .pack()
-    };
-
-    // build transaction
-    let tx = TransactionBuilder::default()
-        .input(input)
-        .outputs(outputs)
-        .outputs_data(outputs_data.pack())
-        .cell_dep(lock_script_dep)
-        .cell_dep(secp256k1_dep)
-        .cell_dep(secp256k1_data_dep)
-        .witness(witness)
+    let sig = privkey.sign_recoverable(&message.into()).expect("sign");
+    let witness = witness_with_mode(Recover::default())
+        .as_builder()
+        .lock(Some(Bytes::from(sig.serialize())).pack())
+        .build();
+    let tx = tx
+        .as_advanced_builder()
+        .set_witnesses(vec![witness.as_bytes().pack()])
         .build();
-    let tx = context.complete_tx(tx);
 
     // run
     let err = context
@@ -414,3 +408,617 @@ fn test_recover_pubkey_with_wrong_signature() {
         .expect_err("pass verification");
     assert_error_eq!(err, ScriptError::ValidationFailure(6),);
 }
+
+#[test]
+fn test_multisig() {
+    let (privkeys, pubkey_hashes) = multisig_keys(3);
+    let multisig_script = multisig_script(1, 2, &pubkey_hashes);
+    let args = hash::blake160(&multisig_script);
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, args.to_vec().into());
+    let tx = multisig_sign_tx(tx, &[&privkeys[2], &privkeys[0]], &multisig_script);
+
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_multisig_without_mode() {
+    let (privkeys, pubkey_hashes) = multisig_keys(3);
+    let multisig_script = multisig_script(1, 2, &pubkey_hashes);
+    let args = hash::blake160(&multisig_script);
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, args.to_vec().into());
+    // `input_type` is absent, same as the witness of the system multisig lock
+    let tx = multisig_sign_tx_with_witness(
+        tx,
+        &[&privkeys[2], &privkeys[0]],
+        &multisig_script,
+        WitnessArgs::default(),
+    );
+
+    // run
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+}
+
+#[test]
+fn test_multisig_with_since() {
+    let (privkeys, pubkey_hashes) = multisig_keys(3);
+    let multisig_script = multisig_script(0, 2, &pubkey_hashes);
+    // absolute block number 100
+    let since = 100;
+
+    let mut context = Context::default();
+    let tx = build_test_tx(
+        &mut context,
+        args_with_since(&hash::blake160(&multisig_script), since),
+    );
+    let tx = set_inputs_since(tx, since);
+    let tx = multisig_sign_tx(tx, &[&privkeys[0], &privkeys[1]], &multisig_script);
+
+    // run
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+}
+
+#[test]
+fn test_multisig_with_unsatisfied_since() {
+    let (privkeys, pubkey_hashes) = multisig_keys(3);
+    let multisig_script = multisig_script(0, 2, &pubkey_hashes);
+    // absolute block number 100
+    let since = 100;
+
+    let mut context = Context::default();
+    let tx = build_test_tx(
+        &mut context,
+        args_with_since(&hash::blake160(&multisig_script), since),
+    );
+    let tx = set_inputs_since(tx, since - 1);
+    let tx = multisig_sign_tx(tx, &[&privkeys[0], &privkeys[1]], &multisig_script);
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(17),);
+}
+
+#[test]
+fn test_multisig_without_required_first_n() {
+    let (privkeys, pubkey_hashes) = multisig_keys(3);
+    let multisig_script = multisig_script(1, 2, &pubkey_hashes);
+    let args = hash::blake160(&multisig_script);
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, args.to_vec().into());
+    // the first pubkey is required
+    let tx = multisig_sign_tx(tx, &[&privkeys[1], &privkeys[2]], &multisig_script);
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(6),);
+}
+
+#[test]
+fn test_multisig_with_duplicated_signature() {
+    let (privkeys, pubkey_hashes) = multisig_keys(3);
+    let multisig_script = multisig_script(0, 2, &pubkey_hashes);
+    let args = hash::blake160(&multisig_script);
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, args.to_vec().into());
+    let tx = multisig_sign_tx(tx, &[&privkeys[1], &privkeys[1]], &multisig_script);
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(6),);
+}
+
+#[test]
+fn test_multisig_with_wrong_witness_length() {
+    let (privkeys, pubkey_hashes) = multisig_keys(3);
+    let multisig_script = multisig_script(0, 2, &pubkey_hashes);
+    let args = hash::blake160(&multisig_script);
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, args.to_vec().into());
+    // threshold is 2, only one signature
+    let tx = multisig_sign_tx(tx, &[&privkeys[0]], &multisig_script);
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(18),);
+}
+
+#[test]
+fn test_multisig_with_invalid_threshold() {
+    let (privkeys, pubkey_hashes) = multisig_keys(3);
+    // threshold is greater than the pubkeys count
+    let multisig_script = multisig_script(0, 4, &pubkey_hashes);
+    let args = hash::blake160(&multisig_script);
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, args.to_vec().into());
+    let tx = multisig_sign_tx(tx, &[&privkeys[0], &privkeys[1]], &multisig_script);
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(13),);
+}
+
+#[test]
+fn test_multisig_with_wrong_script_hash() {
+    let (privkeys, pubkey_hashes) = multisig_keys(3);
+    let multisig_script = multisig_script(0, 2, &pubkey_hashes);
+    // args is the hash of a 2 of 2 script
+    let args = hash::blake160(&multisig_script(0, 2, &pubkey_hashes[..2]));
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, args.to_vec().into());
+    let tx = multisig_sign_tx(tx, &[&privkeys[0], &privkeys[1]], &multisig_script);
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(15),);
+}
+
+#[test]
+fn test_decode_exit_code() {
+    assert_eq!(ExitCode::try_from(13), Ok(ExitCode::InvalidThreshold));
+    assert_eq!(ExitCode::try_from(15), Ok(ExitCode::MultisigScriptHash));
+    // a syscall error the contract doesn't expect
+    assert_eq!(ExitCode::try_from(20), Ok(ExitCode::UnknownSysError));
+    assert_eq!(ExitCode::try_from(0), Err(0));
+    assert_eq!(ExitCode::try_from(22), Err(22));
+    for exit_code in 1..=21 {
+        let err = ExitCode::try_from(exit_code).expect("exit code");
+        assert_eq!(err as i8, exit_code);
+    }
+}
+
+#[test]
+fn test_wrong_args_length() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let mut args = blake160(&pubkey.serialize()).to_vec();
+    args.push(0);
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, args.into());
+    let tx = sign_tx(tx, &privkey);
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(19),);
+}
+
+#[test]
+fn test_sign_without_mode() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let pubkey_hash = blake160(&pubkey.serialize());
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, pubkey_hash.to_vec().into());
+    // `input_type` is absent, same as the witness of the system secp256k1 lock
+    let tx = sign_tx_with_witness(tx, &privkey, WitnessArgs::default());
+
+    // run
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+}
+
+#[test]
+fn test_sign_with_malformed_mode() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let pubkey_hash = blake160(&pubkey.serialize());
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, pubkey_hash.to_vec().into());
+    // `input_type` isn't a `SecpWitness`
+    let witness = WitnessArgs::new_builder()
+        .input_type(Some(Bytes::from(vec![0u8; 4])).pack())
+        .build();
+    let tx = sign_tx_with_witness(tx, &privkey, witness);
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(4),);
+}
+
+#[test]
+fn test_recover_pubkey_with_extra_witness() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let pubkey_hash = blake160(&pubkey.serialize());
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, pubkey_hash.to_vec().into());
+    let tx = push_extra_witness(tx, Bytes::from(vec![42u8; 10]));
+    let tx = sign_tx_with_witness(tx, &privkey, witness_with_mode(Recover::default()));
+
+    // run
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+}
+
+#[test]
+fn test_recover_pubkey_with_modified_extra_witness() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let pubkey_hash = blake160(&pubkey.serialize());
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, pubkey_hash.to_vec().into());
+    let tx = push_extra_witness(tx, Bytes::from(vec![42u8; 10]));
+    let tx = sign_tx_with_witness(tx, &privkey, witness_with_mode(Recover::default()));
+    // the witness without corresponding input is modified after signing
+    let mut witnesses: Vec<packed::Bytes> = tx.witnesses().into_iter().collect();
+    witnesses[1] = Bytes::from(vec![43u8; 10]).pack();
+    let tx = tx.as_advanced_builder().set_witnesses(witnesses).build();
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(6),);
+}
+
+#[test]
+fn test_recover_pubkey_with_empty_group_witness() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let pubkey_hash = blake160(&pubkey.serialize());
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, pubkey_hash.to_vec().into());
+    let tx = add_group_input(&mut context, tx, Bytes::new());
+    let tx = sign_tx_with_witness(tx, &privkey, witness_with_mode(Recover::default()));
+
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_recover_pubkey_with_signed_group_witness() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let pubkey_hash = blake160(&pubkey.serialize());
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, pubkey_hash.to_vec().into());
+    // the second input of the group carries a lock
+    let group_witness = WitnessArgs::new_builder()
+        .lock(Some(Bytes::from(vec![0u8; SIGNATURE_SIZE])).pack())
+        .build();
+    let tx = add_group_input(&mut context, tx, group_witness.as_bytes());
+    let tx = sign_tx_with_witness(tx, &privkey, witness_with_mode(Recover::default()));
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(21),);
+}
+
+#[test]
+fn test_eth_address() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let address = Pubkey::from_slice(&pubkey.serialize())
+        .expect("pubkey")
+        .eth_address()
+        .expect("eth address");
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, address.to_vec().into());
+    let tx = eth_sign_tx(tx, &privkey);
+
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_eth_address_with_wrong_key() {
+    let privkey = Generator::random_privkey();
+    let wrong_privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let address = Pubkey::from_slice(&pubkey.serialize())
+        .expect("pubkey")
+        .eth_address()
+        .expect("eth address");
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, address.to_vec().into());
+    let tx = eth_sign_tx(tx, &wrong_privkey);
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(6),);
+}
+
+#[test]
+fn test_btc_hash160() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let pubkey_hash = hash::hash160(&pubkey.serialize());
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, pubkey_hash.to_vec().into());
+    let tx = btc_sign_tx(tx, &privkey);
+
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_btc_hash160_with_wrong_key() {
+    let privkey = Generator::random_privkey();
+    let wrong_privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let pubkey_hash = hash::hash160(&pubkey.serialize());
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, pubkey_hash.to_vec().into());
+    let tx = btc_sign_tx(tx, &wrong_privkey);
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(6),);
+}
+
+#[test]
+fn test_recover_pubkey_with_since() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let pubkey_hash = blake160(&pubkey.serialize());
+    // absolute block number 100
+    let since = 100;
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, args_with_since(&pubkey_hash, since));
+    let tx = set_inputs_since(tx, since);
+    let tx = sign_tx_with_witness(tx, &privkey, witness_with_mode(Recover::default()));
+
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_recover_pubkey_with_unsatisfied_since() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let pubkey_hash = blake160(&pubkey.serialize());
+    // absolute block number 100
+    let since = 100;
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, args_with_since(&pubkey_hash, since));
+    let tx = set_inputs_since(tx, since - 1);
+    let tx = sign_tx_with_witness(tx, &privkey, witness_with_mode(Recover::default()));
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(17),);
+}
+
+#[test]
+fn test_sighash_all_with_incorrect_since_flags() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let pubkey_hash = blake160(&pubkey.serialize());
+    // absolute epoch 1
+    let since = 0x2000_0100_0000_0001;
+
+    let mut context = Context::default();
+    let tx = build_test_tx(&mut context, args_with_since(&pubkey_hash, since));
+    // absolute block number
+    let tx = set_inputs_since(tx, 1);
+    let tx = sign_tx(tx, &privkey);
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(16),);
+}
+
+/// Build a transaction spending a cell locked by the `secp256k1-loader` script,
+/// the library cell is deployed with `secp256k1_type` as its type script if `with_library` is set
+fn build_loader_tx(
+    context: &mut Context,
+    lock_args: Bytes,
+    with_library: bool,
+    secp256k1_type: Option<Script>,
+) -> TransactionView {
+    let contract_bin: Bytes = Loader::default().load_binary("secp256k1-loader");
+    let out_point = context.deploy_cell(contract_bin);
+    let lock_script = context.build_script(&out_point, lock_args).expect("script");
+    let lock_script_dep = CellDep::new_builder().out_point(out_point).build();
+
+    let mut tx_builder = TransactionBuilder::default().cell_dep(lock_script_dep);
+    if with_library {
+        let secp256k1_bin: Bytes =
+            fs::read("../ckb-miscellaneous-scripts/build/secp256k1_blake2b_sighash_all_dual")
+                .expect("load secp256k1")
+                .into();
+        let secp256k1_out_point = context.create_cell(
+            CellOutput::new_builder()
+                .capacity((secp256k1_bin.len() as u64).pack())
+                .type_(secp256k1_type.pack())
+                .build(),
+            secp256k1_bin,
+        );
+        tx_builder = tx_builder.cell_dep(
+            CellDep::new_builder()
+                .out_point(secp256k1_out_point)
+                .build(),
+        );
+    }
+
+    let input_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(1000u64.pack())
+            .lock(lock_script.clone())
+            .build(),
+        Bytes::new(),
+    );
+    let input = CellInput::new_builder()
+        .previous_output(input_out_point)
+        .build();
+    let tx = tx_builder
+        .input(input)
+        .output(
+            CellOutput::new_builder()
+                .capacity(1000u64.pack())
+                .lock(lock_script)
+                .build(),
+        )
+        .output_data(Bytes::new().pack())
+        .build();
+    context.complete_tx(tx)
+}
+
+/// A type script standing for the type id of the library cell, type scripts of cell deps are not executed
+fn random_type_script() -> Script {
+    let mut rng = thread_rng();
+    let mut code_hash = [0u8; 32];
+    rng.fill(&mut code_hash);
+    let mut args = [0u8; 32];
+    rng.fill(&mut args);
+    Script::new_builder()
+        .code_hash(code_hash.pack())
+        .hash_type(ScriptHashType::Type.into())
+        .args(Bytes::from(args.to_vec()).pack())
+        .build()
+}
+
+/// Args of the `secp256k1-loader` script: code hash | hash type
+fn loader_args(code_hash: &[u8], hash_type: u8) -> Bytes {
+    let mut args = code_hash.to_vec();
+    args.push(hash_type);
+    args.into()
+}
+
+#[test]
+fn test_load_by_type_hash() {
+    let type_script = random_type_script();
+    let args = loader_args(type_script.calc_script_hash().as_slice(), 1);
+
+    let mut context = Context::default();
+    let tx = build_loader_tx(&mut context, args, true, Some(type_script));
+
+    // run
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+}
+
+#[test]
+fn test_load_by_type_hash_without_library() {
+    let type_script = random_type_script();
+    let args = loader_args(type_script.calc_script_hash().as_slice(), 1);
+
+    let mut context = Context::default();
+    // the library is deployed with another type script
+    let tx = build_loader_tx(&mut context, args, true, Some(random_type_script()));
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(5),);
+}
+
+#[test]
+fn test_load_by_data_hash() {
+    let args = loader_args(&CODE_HASH_SECP256K1, 0);
+
+    let mut context = Context::default();
+    let tx = build_loader_tx(&mut context, args, true, None);
+
+    // run
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+}
+
+#[test]
+fn test_load_by_data_hash_without_library() {
+    let args = loader_args(&CODE_HASH_SECP256K1, 0);
+
+    let mut context = Context::default();
+    let tx = build_loader_tx(&mut context, args, false, None);
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(5),);
+}
+
+#[test]
+fn test_load_any() {
+    let mut context = Context::default();
+    let tx = build_loader_tx(&mut context, Bytes::new(), true, None);
+
+    // run
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+}
+
+#[test]
+fn test_load_any_without_library() {
+    let mut context = Context::default();
+    let tx = build_loader_tx(&mut context, Bytes::new(), false, None);
+
+    // run
+    let err = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect_err("pass verification");
+    assert_error_eq!(err, ScriptError::ValidationFailure(5),);
+}
@@ -0,0 +1,103 @@
+#[cfg(target_arch = "riscv64")]
+use ckb_std::{debug, error::SysError};
+use core::convert::TryFrom;
+
+/// Error, the discriminant is the exit code of the script
+#[repr(i8)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Error {
+    /// Syscall: index out of bound
+    IndexOutOfBound = 1,
+    /// Syscall: item missing
+    ItemMissing = 2,
+    /// Syscall: buffer length not enough
+    LengthNotEnough = 3,
+    /// Syscall: data encoding error, or malformed args and witness
+    Encoding = 4,
+    // Add customized errors here...
+    /// The shared library rejects the transaction
+    Secp256k1 = 5,
+    /// The recovered pubkey doesn't match the args
+    WrongPubkey = 6,
+    /// Failed to load the secp256k1 prefilled data cell
+    LoadPrefilledData = 7,
+    /// Failed to recover the pubkey from the signature
+    RecoverPubkey = 8,
+    /// Failed to load the shared library
+    LoadLibrary = 9,
+    /// Failed to compute the sighash-all message
+    SighashAll = 10,
+    /// Multisig: the reserved field is not 0
+    InvalidReserveField = 11,
+    /// Multisig: pubkeys count is 0
+    InvalidPubkeysCnt = 12,
+    /// Multisig: threshold is 0 or greater than pubkeys count
+    InvalidThreshold = 13,
+    /// Multisig: require_first_n is greater than threshold
+    InvalidRequireFirstN = 14,
+    /// Multisig: the hash of the multisig script doesn't match the args
+    MultisigScriptHash = 15,
+    /// Since: the flags of the input since differ from the args
+    IncorrectSinceFlags = 16,
+    /// Since: the input since is less than the args
+    IncorrectSinceValue = 17,
+    /// The witness lock has a wrong length
+    WitnessLength = 18,
+    /// The script args have a wrong length
+    ArgsLength = 19,
+    /// Syscall: unknown error code
+    UnknownSysError = 20,
+    /// An additional witness of the script group has a `lock`
+    GroupWitness = 21,
+}
+
+/// Decode the exit code of a rejected transaction, returns the code if it's unknown
+impl TryFrom<i8> for Error {
+    type Error = i8;
+
+    fn try_from(exit_code: i8) -> Result<Self, i8> {
+        use Error::*;
+        let err = match exit_code {
+            1 => IndexOutOfBound,
+            2 => ItemMissing,
+            3 => LengthNotEnough,
+            4 => Encoding,
+            5 => Secp256k1,
+            6 => WrongPubkey,
+            7 => LoadPrefilledData,
+            8 => RecoverPubkey,
+            9 => LoadLibrary,
+            10 => SighashAll,
+            11 => InvalidReserveField,
+            12 => InvalidPubkeysCnt,
+            13 => InvalidThreshold,
+            14 => InvalidRequireFirstN,
+            15 => MultisigScriptHash,
+            16 => IncorrectSinceFlags,
+            17 => IncorrectSinceValue,
+            18 => WitnessLength,
+            19 => ArgsLength,
+            20 => UnknownSysError,
+            21 => GroupWitness,
+            exit_code => return Err(exit_code),
+        };
+        Ok(err)
+    }
+}
+
+#[cfg(target_arch = "riscv64")]
+impl From<SysError> for Error {
+    fn from(err: SysError) -> Self {
+        use SysError::*;
+        match err {
+            IndexOutOfBound => Self::IndexOutOfBound,
+            ItemMissing => Self::ItemMissing,
+            LengthNotEnough(_) => Self::LengthNotEnough,
+            Encoding => Self::Encoding,
+            Unknown(err_code) => {
+                debug!("unexpected sys error {}", err_code);
+                Self::UnknownSysError
+            }
+        }
+    }
+}
@@ -0,0 +1,16 @@
+//! Exit codes of the contract, and molecule types of the witness generated from `schemas/secp_witness.mol`:
+//!
+//! ```shell
+//! moleculec --language rust --schema-file schemas/secp_witness.mol > src/secp_witness.rs
+//! ```
+
+#![no_std]
+#[macro_use]
+extern crate alloc;
+
+mod error;
+#[allow(clippy::all)]
+mod secp_witness;
+
+pub use error::Error;
+pub use secp_witness::*;
@@ -0,0 +1,1255 @@
+// Generated by Molecule 0.6.1
+
+use molecule::prelude::*;
+#[derive(Clone)]
+pub struct SighashAll(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for SighashAll {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for SighashAll {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for SighashAll {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ".. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for SighashAll {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![4, 0, 0, 0];
+        SighashAll::new_unchecked(v.into())
+    }
+}
+impl SighashAll {
+    pub const FIELD_COUNT: usize = 0;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn as_reader<'r>(&'r self) -> SighashAllReader<'r> {
+        SighashAllReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for SighashAll {
+    type Builder = SighashAllBuilder;
+    const NAME: &'static str = "SighashAll";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        SighashAll(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        SighashAllReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        SighashAllReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+    }
+}
+#[derive(Clone, Copy)]
+pub struct SighashAllReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for SighashAllReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for SighashAllReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for SighashAllReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ".. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> SighashAllReader<'r> {
+    pub const FIELD_COUNT: usize = 0;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for SighashAllReader<'r> {
+    type Entity = SighashAll;
+    const NAME: &'static str = "SighashAllReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        SighashAllReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len > molecule::NUMBER_SIZE && !compatible {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, !0);
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct SighashAllBuilder {}
+impl molecule::prelude::Builder for SighashAllBuilder {
+    type Entity = SighashAll;
+    const NAME: &'static str = "SighashAllBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(&molecule::pack_number(
+            molecule::NUMBER_SIZE as molecule::Number,
+        ))?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        SighashAll::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct Recover(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for Recover {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for Recover {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for Recover {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ".. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for Recover {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![4, 0, 0, 0];
+        Recover::new_unchecked(v.into())
+    }
+}
+impl Recover {
+    pub const FIELD_COUNT: usize = 0;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn as_reader<'r>(&'r self) -> RecoverReader<'r> {
+        RecoverReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for Recover {
+    type Builder = RecoverBuilder;
+    const NAME: &'static str = "Recover";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        Recover(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        RecoverReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        RecoverReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+    }
+}
+#[derive(Clone, Copy)]
+pub struct RecoverReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for RecoverReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for RecoverReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for RecoverReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ".. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> RecoverReader<'r> {
+    pub const FIELD_COUNT: usize = 0;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for RecoverReader<'r> {
+    type Entity = Recover;
+    const NAME: &'static str = "RecoverReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        RecoverReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len > molecule::NUMBER_SIZE && !compatible {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, !0);
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct RecoverBuilder {}
+impl molecule::prelude::Builder for RecoverBuilder {
+    type Entity = Recover;
+    const NAME: &'static str = "RecoverBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(&molecule::pack_number(
+            molecule::NUMBER_SIZE as molecule::Number,
+        ))?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        Recover::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct Multisig(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for Multisig {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for Multisig {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for Multisig {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ".. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for Multisig {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![4, 0, 0, 0];
+        Multisig::new_unchecked(v.into())
+    }
+}
+impl Multisig {
+    pub const FIELD_COUNT: usize = 0;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn as_reader<'r>(&'r self) -> MultisigReader<'r> {
+        MultisigReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for Multisig {
+    type Builder = MultisigBuilder;
+    const NAME: &'static str = "Multisig";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        Multisig(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        MultisigReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        MultisigReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+    }
+}
+#[derive(Clone, Copy)]
+pub struct MultisigReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for MultisigReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for MultisigReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for MultisigReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ".. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> MultisigReader<'r> {
+    pub const FIELD_COUNT: usize = 0;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for MultisigReader<'r> {
+    type Entity = Multisig;
+    const NAME: &'static str = "MultisigReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        MultisigReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len > molecule::NUMBER_SIZE && !compatible {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, !0);
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct MultisigBuilder {}
+impl molecule::prelude::Builder for MultisigBuilder {
+    type Entity = Multisig;
+    const NAME: &'static str = "MultisigBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(&molecule::pack_number(
+            molecule::NUMBER_SIZE as molecule::Number,
+        ))?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        Multisig::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct Eth(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for Eth {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for Eth {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for Eth {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ".. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for Eth {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![4, 0, 0, 0];
+        Eth::new_unchecked(v.into())
+    }
+}
+impl Eth {
+    pub const FIELD_COUNT: usize = 0;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn as_reader<'r>(&'r self) -> EthReader<'r> {
+        EthReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for Eth {
+    type Builder = EthBuilder;
+    const NAME: &'static str = "Eth";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        Eth(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        EthReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        EthReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+    }
+}
+#[derive(Clone, Copy)]
+pub struct EthReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for EthReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for EthReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for EthReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ".. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> EthReader<'r> {
+    pub const FIELD_COUNT: usize = 0;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for EthReader<'r> {
+    type Entity = Eth;
+    const NAME: &'static str = "EthReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        EthReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len > molecule::NUMBER_SIZE && !compatible {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, !0);
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct EthBuilder {}
+impl molecule::prelude::Builder for EthBuilder {
+    type Entity = Eth;
+    const NAME: &'static str = "EthBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(&molecule::pack_number(
+            molecule::NUMBER_SIZE as molecule::Number,
+        ))?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        Eth::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct Btc(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for Btc {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for Btc {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for Btc {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ".. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for Btc {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![4, 0, 0, 0];
+        Btc::new_unchecked(v.into())
+    }
+}
+impl Btc {
+    pub const FIELD_COUNT: usize = 0;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn as_reader<'r>(&'r self) -> BtcReader<'r> {
+        BtcReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for Btc {
+    type Builder = BtcBuilder;
+    const NAME: &'static str = "Btc";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        Btc(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        BtcReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        BtcReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+    }
+}
+#[derive(Clone, Copy)]
+pub struct BtcReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for BtcReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for BtcReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for BtcReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ".. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> BtcReader<'r> {
+    pub const FIELD_COUNT: usize = 0;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for BtcReader<'r> {
+    type Entity = Btc;
+    const NAME: &'static str = "BtcReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        BtcReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len > molecule::NUMBER_SIZE && !compatible {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, !0);
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct BtcBuilder {}
+impl molecule::prelude::Builder for BtcBuilder {
+    type Entity = Btc;
+    const NAME: &'static str = "BtcBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(&molecule::pack_number(
+            molecule::NUMBER_SIZE as molecule::Number,
+        ))?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        Btc::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct SecpWitness(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for SecpWitness {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for SecpWitness {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for SecpWitness {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}(", Self::NAME)?;
+        self.to_enum().display_inner(f)?;
+        write!(f, ")")
+    }
+}
+impl ::core::default::Default for SecpWitness {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![0, 0, 0, 0, 4, 0, 0, 0];
+        SecpWitness::new_unchecked(v.into())
+    }
+}
+impl SecpWitness {
+    pub const ITEMS_COUNT: usize = 5;
+    pub fn item_id(&self) -> molecule::Number {
+        molecule::unpack_number(self.as_slice())
+    }
+    pub fn to_enum(&self) -> SecpWitnessUnion {
+        let inner = self.0.slice(molecule::NUMBER_SIZE..);
+        match self.item_id() {
+            0 => SighashAll::new_unchecked(inner).into(),
+            1 => Recover::new_unchecked(inner).into(),
+            2 => Multisig::new_unchecked(inner).into(),
+            3 => Eth::new_unchecked(inner).into(),
+            4 => Btc::new_unchecked(inner).into(),
+            _ => panic!("{}: invalid data", Self::NAME),
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> SecpWitnessReader<'r> {
+        SecpWitnessReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for SecpWitness {
+    type Builder = SecpWitnessBuilder;
+    const NAME: &'static str = "SecpWitness";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        SecpWitness(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        SecpWitnessReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        SecpWitnessReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().set(self.to_enum())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct SecpWitnessReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for SecpWitnessReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for SecpWitnessReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for SecpWitnessReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}(", Self::NAME)?;
+        self.to_enum().display_inner(f)?;
+        write!(f, ")")
+    }
+}
+impl<'r> SecpWitnessReader<'r> {
+    pub const ITEMS_COUNT: usize = 5;
+    pub fn item_id(&self) -> molecule::Number {
+        molecule::unpack_number(self.as_slice())
+    }
+    pub fn to_enum(&self) -> SecpWitnessUnionReader<'r> {
+        let inner = &self.as_slice()[molecule::NUMBER_SIZE..];
+        match self.item_id() {
+            0 => SighashAllReader::new_unchecked(inner).into(),
+            1 => RecoverReader::new_unchecked(inner).into(),
+            2 => MultisigReader::new_unchecked(inner).into(),
+            3 => EthReader::new_unchecked(inner).into(),
+            4 => BtcReader::new_unchecked(inner).into(),
+            _ => panic!("{}: invalid data", Self::NAME),
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for SecpWitnessReader<'r> {
+    type Entity = SecpWitness;
+    const NAME: &'static str = "SecpWitnessReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        SecpWitnessReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let item_id = molecule::unpack_number(slice);
+        let inner_slice = &slice[molecule::NUMBER_SIZE..];
+        match item_id {
+            0 => SighashAllReader::verify(inner_slice, compatible),
+            1 => RecoverReader::verify(inner_slice, compatible),
+            2 => MultisigReader::verify(inner_slice, compatible),
+            3 => EthReader::verify(inner_slice, compatible),
+            4 => BtcReader::verify(inner_slice, compatible),
+            _ => ve!(Self, UnknownItem, Self::ITEMS_COUNT, item_id),
+        }?;
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct SecpWitnessBuilder(pub(crate) SecpWitnessUnion);
+impl SecpWitnessBuilder {
+    pub const ITEMS_COUNT: usize = 5;
+    pub fn set<I>(mut self, v: I) -> Self
+    where
+        I: ::core::convert::Into<SecpWitnessUnion>,
+    {
+        self.0 = v.into();
+        self
+    }
+}
+impl molecule::prelude::Builder for SecpWitnessBuilder {
+    type Entity = SecpWitness;
+    const NAME: &'static str = "SecpWitnessBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE + self.0.as_slice().len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(&molecule::pack_number(self.0.item_id()))?;
+        writer.write_all(self.0.as_slice())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        SecpWitness::new_unchecked(inner.into())
+    }
+}
+#[derive(Debug, Clone)]
+pub enum SecpWitnessUnion {
+    SighashAll(SighashAll),
+    Recover(Recover),
+    Multisig(Multisig),
+    Eth(Eth),
+    Btc(Btc),
+}
+#[derive(Debug, Clone, Copy)]
+pub enum SecpWitnessUnionReader<'r> {
+    SighashAll(SighashAllReader<'r>),
+    Recover(RecoverReader<'r>),
+    Multisig(MultisigReader<'r>),
+    Eth(EthReader<'r>),
+    Btc(BtcReader<'r>),
+}
+impl ::core::default::Default for SecpWitnessUnion {
+    fn default() -> Self {
+        SecpWitnessUnion::SighashAll(::core::default::Default::default())
+    }
+}
+impl ::core::fmt::Display for SecpWitnessUnion {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            SecpWitnessUnion::SighashAll(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, SighashAll::NAME, item)
+            }
+            SecpWitnessUnion::Recover(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, Recover::NAME, item)
+            }
+            SecpWitnessUnion::Multisig(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, Multisig::NAME, item)
+            }
+            SecpWitnessUnion::Eth(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, Eth::NAME, item)
+            }
+            SecpWitnessUnion::Btc(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, Btc::NAME, item)
+            }
+        }
+    }
+}
+impl<'r> ::core::fmt::Display for SecpWitnessUnionReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            SecpWitnessUnionReader::SighashAll(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, SighashAllReader::NAME, item)
+            }
+            SecpWitnessUnionReader::Recover(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, RecoverReader::NAME, item)
+            }
+            SecpWitnessUnionReader::Multisig(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, MultisigReader::NAME, item)
+            }
+            SecpWitnessUnionReader::Eth(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, EthReader::NAME, item)
+            }
+            SecpWitnessUnionReader::Btc(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, BtcReader::NAME, item)
+            }
+        }
+    }
+}
+impl SecpWitnessUnion {
+    pub(crate) fn display_inner(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            SecpWitnessUnion::SighashAll(ref item) => write!(f, "{}", item),
+            SecpWitnessUnion::Recover(ref item) => write!(f, "{}", item),
+            SecpWitnessUnion::Multisig(ref item) => write!(f, "{}", item),
+            SecpWitnessUnion::Eth(ref item) => write!(f, "{}", item),
+            SecpWitnessUnion::Btc(ref item) => write!(f, "{}", item),
+        }
+    }
+}
+impl<'r> SecpWitnessUnionReader<'r> {
+    pub(crate) fn display_inner(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            SecpWitnessUnionReader::SighashAll(ref item) => write!(f, "{}", item),
+            SecpWitnessUnionReader::Recover(ref item) => write!(f, "{}", item),
+            SecpWitnessUnionReader::Multisig(ref item) => write!(f, "{}", item),
+            SecpWitnessUnionReader::Eth(ref item) => write!(f, "{}", item),
+            SecpWitnessUnionReader::Btc(ref item) => write!(f, "{}", item),
+        }
+    }
+}
+impl ::core::convert::From<SighashAll> for SecpWitnessUnion {
+    fn from(item: SighashAll) -> Self {
+        SecpWitnessUnion::SighashAll(item)
+    }
+}
+impl ::core::convert::From<Recover> for SecpWitnessUnion {
+    fn from(item: Recover) -> Self {
+        SecpWitnessUnion::Recover(item)
+    }
+}
+impl ::core::convert::From<Multisig> for SecpWitnessUnion {
+    fn from(item: Multisig) -> Self {
+        SecpWitnessUnion::Multisig(item)
+    }
+}
+impl ::core::convert::From<Eth> for SecpWitnessUnion {
+    fn from(item: Eth) -> Self {
+        SecpWitnessUnion::Eth(item)
+    }
+}
+impl ::core::convert::From<Btc> for SecpWitnessUnion {
+    fn from(item: Btc) -> Self {
+        SecpWitnessUnion::Btc(item)
+    }
+}
+impl<'r> ::core::convert::From<SighashAllReader<'r>> for SecpWitnessUnionReader<'r> {
+    fn from(item: SighashAllReader<'r>) -> Self {
+        SecpWitnessUnionReader::SighashAll(item)
+    }
+}
+impl<'r> ::core::convert::From<RecoverReader<'r>> for SecpWitnessUnionReader<'r> {
+    fn from(item: RecoverReader<'r>) -> Self {
+        SecpWitnessUnionReader::Recover(item)
+    }
+}
+impl<'r> ::core::convert::From<MultisigReader<'r>> for SecpWitnessUnionReader<'r> {
+    fn from(item: MultisigReader<'r>) -> Self {
+        SecpWitnessUnionReader::Multisig(item)
+    }
+}
+impl<'r> ::core::convert::From<EthReader<'r>> for SecpWitnessUnionReader<'r> {
+    fn from(item: EthReader<'r>) -> Self {
+        SecpWitnessUnionReader::Eth(item)
+    }
+}
+impl<'r> ::core::convert::From<BtcReader<'r>> for SecpWitnessUnionReader<'r> {
+    fn from(item: BtcReader<'r>) -> Self {
+        SecpWitnessUnionReader::Btc(item)
+    }
+}
+impl SecpWitnessUnion {
+    pub const NAME: &'static str = "SecpWitnessUnion";
+    pub fn as_bytes(&self) -> molecule::bytes::Bytes {
+        match self {
+            SecpWitnessUnion::SighashAll(item) => item.as_bytes(),
+            SecpWitnessUnion::Recover(item) => item.as_bytes(),
+            SecpWitnessUnion::Multisig(item) => item.as_bytes(),
+            SecpWitnessUnion::Eth(item) => item.as_bytes(),
+            SecpWitnessUnion::Btc(item) => item.as_bytes(),
+        }
+    }
+    pub fn as_slice(&self) -> &[u8] {
+        match self {
+            SecpWitnessUnion::SighashAll(item) => item.as_slice(),
+            SecpWitnessUnion::Recover(item) => item.as_slice(),
+            SecpWitnessUnion::Multisig(item) => item.as_slice(),
+            SecpWitnessUnion::Eth(item) => item.as_slice(),
+            SecpWitnessUnion::Btc(item) => item.as_slice(),
+        }
+    }
+    pub fn item_id(&self) -> molecule::Number {
+        match self {
+            SecpWitnessUnion::SighashAll(_) => 0,
+            SecpWitnessUnion::Recover(_) => 1,
+            SecpWitnessUnion::Multisig(_) => 2,
+            SecpWitnessUnion::Eth(_) => 3,
+            SecpWitnessUnion::Btc(_) => 4,
+        }
+    }
+    pub fn item_name(&self) -> &str {
+        match self {
+            SecpWitnessUnion::SighashAll(_) => "SighashAll",
+            SecpWitnessUnion::Recover(_) => "Recover",
+            SecpWitnessUnion::Multisig(_) => "Multisig",
+            SecpWitnessUnion::Eth(_) => "Eth",
+            SecpWitnessUnion::Btc(_) => "Btc",
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> SecpWitnessUnionReader<'r> {
+        match self {
+            SecpWitnessUnion::SighashAll(item) => item.as_reader().into(),
+            SecpWitnessUnion::Recover(item) => item.as_reader().into(),
+            SecpWitnessUnion::Multisig(item) => item.as_reader().into(),
+            SecpWitnessUnion::Eth(item) => item.as_reader().into(),
+            SecpWitnessUnion::Btc(item) => item.as_reader().into(),
+        }
+    }
+}
+impl<'r> SecpWitnessUnionReader<'r> {
+    pub const NAME: &'r str = "SecpWitnessUnionReader";
+    pub fn as_slice(&self) -> &'r [u8] {
+        match self {
+            SecpWitnessUnionReader::SighashAll(item) => item.as_slice(),
+            SecpWitnessUnionReader::Recover(item) => item.as_slice(),
+            SecpWitnessUnionReader::Multisig(item) => item.as_slice(),
+            SecpWitnessUnionReader::Eth(item) => item.as_slice(),
+            SecpWitnessUnionReader::Btc(item) => item.as_slice(),
+        }
+    }
+    pub fn item_id(&self) -> molecule::Number {
+        match self {
+            SecpWitnessUnionReader::SighashAll(_) => 0,
+            SecpWitnessUnionReader::Recover(_) => 1,
+            SecpWitnessUnionReader::Multisig(_) => 2,
+            SecpWitnessUnionReader::Eth(_) => 3,
+            SecpWitnessUnionReader::Btc(_) => 4,
+        }
+    }
+    pub fn item_name(&self) -> &str {
+        match self {
+            SecpWitnessUnionReader::SighashAll(_) => "SighashAll",
+            SecpWitnessUnionReader::Recover(_) => "Recover",
+            SecpWitnessUnionReader::Multisig(_) => "Multisig",
+            SecpWitnessUnionReader::Eth(_) => "Eth",
+            SecpWitnessUnionReader::Btc(_) => "Btc",
+        }
+    }
+}